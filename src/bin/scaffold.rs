@@ -16,30 +16,33 @@ pub fn part_two(input: &str) -> Option<u32> {
     None
 }
 
-fn main() {
-    let input = &advent_of_code::read_file("inputs", DAY);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", DAY);
+        let input = crate::read_file("examples", DAY);
         assert_eq!(part_one(&input), None);
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", DAY);
+        let input = crate::read_file("examples", DAY);
         assert_eq!(part_two(&input), None);
     }
 }
 "###;
 
+const BIN_TEMPLATE: &str = r###"use advent_of_code::days::dayPADDED::{part_one, part_two};
+
+fn main() {
+    let input = &advent_of_code::read_file("inputs", DAY);
+    advent_of_code::solve!(1, part_one, input);
+    advent_of_code::solve!(2, part_two, input);
+}
+"###;
+
 fn parse_args() -> Result<u8, pico_args::Error> {
     let mut args = pico_args::Arguments::from_env();
     args.free_from_str()
@@ -49,8 +52,16 @@ fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
+fn append_file(path: &str) -> Result<File, std::io::Error> {
+    OpenOptions::new().append(true).open(path)
+}
+
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
 }
 
 fn main() {
@@ -66,7 +77,9 @@ fn main() {
 
     let input_path = format!("src/inputs/{}.txt", day_padded);
     let example_path = format!("src/examples/{}.txt", day_padded);
-    let module_path = format!("src/bin/{}.rs", day_padded);
+    let module_path = format!("src/days/day{}.rs", day_padded);
+    let bin_path = format!("src/bin/{}.rs", day_padded);
+    let days_path = "src/days/mod.rs";
 
     let mut file = match safe_create_file(&module_path) {
         Ok(file) => file,
@@ -86,6 +99,30 @@ fn main() {
         }
     }
 
+    let bin = BIN_TEMPLATE
+        .replace("PADDED", &day_padded)
+        .replace("DAY", &day.to_string());
+    match safe_create_file(&bin_path).and_then(|mut file| file.write_all(bin.as_bytes())) {
+        Ok(_) => {
+            println!("Created binary file \"{}\"", &bin_path);
+        }
+        Err(e) => {
+            eprintln!("Failed to create binary file: {}", e);
+            process::exit(1);
+        }
+    }
+
+    let declaration = format!("pub mod day{};\n", day_padded);
+    match append_file(days_path).and_then(|mut file| file.write_all(declaration.as_bytes())) {
+        Ok(_) => {
+            println!("Registered module in \"{}\"", days_path);
+        }
+        Err(e) => {
+            eprintln!("Failed to register module: {}", e);
+            process::exit(1);
+        }
+    }
+
     match create_file(&input_path) {
         Ok(_) => {
             println!("Created empty input file \"{}\"", &input_path);
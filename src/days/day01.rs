@@ -0,0 +1,39 @@
+use itertools::Itertools;
+
+pub fn both_parts(input: &str) -> (Option<u32>, Option<u32>) {
+    let calories = input
+        .lines()
+        .group_by(|line| line.is_empty())
+        .into_iter()
+        .map(|group| {
+            group
+                .1
+                .filter_map(|line| line.parse::<u32>().ok())
+                .sum::<u32>()
+        })
+        .sorted()
+        .rev()
+        .collect_vec();
+
+    (
+        calories.first().copied(),
+        Some(calories.iter().take(3).sum()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 1);
+        assert_eq!(both_parts(&input).0, Some(24000));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 1);
+        assert_eq!(both_parts(&input).1, Some(45000));
+    }
+}
@@ -0,0 +1,74 @@
+pub struct Section {
+    pub lower: u32,
+    pub higher: u32,
+}
+
+impl Section {
+    pub fn new(lower: u32, higher: u32) -> Section {
+        Section { lower, higher }
+    }
+
+    pub fn contains(&self, other: &Section) -> bool {
+        self.lower <= other.lower && self.higher >= other.higher
+            || self.lower >= other.lower && self.higher <= other.higher
+    }
+
+    pub fn overlaps(&self, other: &Section) -> bool {
+        self.lower <= other.lower && other.lower <= self.higher
+            || other.lower <= self.lower && self.lower <= other.higher
+    }
+
+    pub fn parse(line: &str) -> (Section, Section) {
+        let sections: Vec<&str> = line.split(',').collect();
+        let first: Vec<_> = sections[0].split('-').collect();
+        let second: Vec<_> = sections[1].split('-').collect();
+
+        (
+            Section::new(
+                first[0].parse().expect("Should be a number"),
+                first[1].parse().expect("Should be a number"),
+            ),
+            Section::new(
+                second[0].parse().expect("Should be a number"),
+                second[1].parse().expect("Should be a number"),
+            ),
+        )
+    }
+}
+
+pub fn part_one(input: &str) -> Option<u32> {
+    let count = input
+        .lines()
+        .map(Section::parse)
+        .filter(|s| s.0.contains(&s.1))
+        .count();
+
+    Some(count as u32)
+}
+
+pub fn part_two(input: &str) -> Option<u32> {
+    let count = input
+        .lines()
+        .map(Section::parse)
+        .filter(|s| s.0.overlaps(&s.1))
+        .count();
+
+    Some(count as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 4);
+        assert_eq!(part_one(&input), Some(2));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 4);
+        assert_eq!(part_two(&input), Some(4));
+    }
+}
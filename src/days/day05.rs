@@ -0,0 +1,235 @@
+/*
+ * Stacks are vectors where the last crate is the top of the stack
+ */
+
+use std::fmt::Display;
+
+use itertools::Itertools;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// The CrateMover 9000 moves crates one at a time.
+    OneAtATime,
+    /// The CrateMover 9001 moves several crates at once, keeping their order.
+    Multiple,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Stack {
+    pub crates: Vec<String>,
+}
+
+impl Stack {
+    pub fn top(&self) -> Option<&str> {
+        self.crates.last().map(|c| c.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    pub count: usize,
+    pub from: usize,
+    pub to: usize,
+}
+
+impl Move {
+    pub fn from_line(line: &str) -> Option<Move> {
+        let (_, count, _, from, _, to) = line.split_ascii_whitespace().next_tuple()?;
+        Some(Move {
+            count: count.parse().ok()?,
+            from: from.parse().ok()?,
+            to: to.parse().ok()?,
+        })
+    }
+}
+
+/// The stacks in the order of the drawing, together with the id written below each.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Crane {
+    pub ids: Vec<usize>,
+    pub stacks: Vec<Stack>,
+}
+
+/// The start and end columns of each run of characters in `line` matching `is_part`.
+fn spans(line: &str, is_part: impl Fn(char) -> bool) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut start = None;
+    for (i, c) in line.chars().chain([' ']).enumerate() {
+        match (start, is_part(c)) {
+            (None, true) => start = Some(i),
+            (Some(s), false) => {
+                spans.push((s, i));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    spans
+}
+
+impl Crane {
+    /// Parses the drawing, matching each crate to the stack id it is drawn above.
+    pub fn from_drawing(drawing: &str) -> Crane {
+        let mut lines = drawing.lines().collect_vec();
+        let numbers = lines.pop().expect("Expected the stack ids!");
+        let columns = spans(numbers, |c| c.is_ascii_digit());
+
+        let ids = columns
+            .iter()
+            .map(|&(start, end)| numbers[start..end].parse().unwrap())
+            .collect();
+        let mut stacks = vec![Stack::default(); columns.len()];
+
+        for line in lines.iter().rev() {
+            for (start, end) in spans(line, |c| !c.is_whitespace()) {
+                let label = line[start..end]
+                    .trim_start_matches('[')
+                    .trim_end_matches(']');
+                let stack = columns
+                    .iter()
+                    .position(|&(s, e)| s < end && start < e)
+                    .unwrap_or_else(|| panic!("Crate {} is not above a stack", label));
+                stacks[stack].crates.push(label.to_owned());
+            }
+        }
+
+        Crane { ids, stacks }
+    }
+
+    fn index(&self, id: usize) -> usize {
+        self.ids
+            .iter()
+            .position(|&i| i == id)
+            .unwrap_or_else(|| panic!("No stack {}", id))
+    }
+
+    pub fn apply(&mut self, step: Move, mode: Mode) {
+        let (from, to) = (self.index(step.from), self.index(step.to));
+        let from = &mut self.stacks[from].crates;
+        let mut moved = from.split_off(from.len() - step.count);
+        if mode == Mode::OneAtATime {
+            moved.reverse();
+        }
+        self.stacks[to].crates.extend(moved);
+    }
+
+    pub fn tops(&self) -> String {
+        self.stacks.iter().filter_map(Stack::top).collect()
+    }
+}
+
+/// Draws the stacks the way the puzzle does, widening the cells for longer labels.
+impl Display for Crane {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let labels = self
+            .stacks
+            .iter()
+            .flat_map(|s| s.crates.iter().map(String::len));
+        let ids = self.ids.iter().map(|id| id.to_string().len());
+        let width = labels.map(|l| l + 2).chain(ids).max().unwrap_or(3);
+        let height = self
+            .stacks
+            .iter()
+            .map(|s| s.crates.len())
+            .max()
+            .unwrap_or(0);
+
+        for level in (0..height).rev() {
+            let mut row = self.stacks.iter().map(|s| match s.crates.get(level) {
+                Some(label) => format!("{:<width$}", format!("[{}]", label)),
+                None => " ".repeat(width),
+            });
+            writeln!(f, "{}", row.join(" "))?;
+        }
+
+        let mut ids = self.ids.iter().map(|id| format!("{:^width$}", id));
+        write!(f, "{}", ids.join(" "))
+    }
+}
+
+pub fn parse_initial<'a>(mut lines: impl Iterator<Item = &'a str>) -> Crane {
+    Crane::from_drawing(&lines.join("\n"))
+}
+
+pub fn solve(input: &str, mode: Mode) -> Option<String> {
+    let mut lines = input.lines();
+    let mut crane = parse_initial(lines.take_while_ref(|line| !line.is_empty()));
+
+    // skip empty line
+    lines.next();
+
+    for line in lines {
+        if let Some(step) = Move::from_line(line) {
+            crane.apply(step, mode);
+            crate::trace!("{}\n{}", line, crane);
+        }
+    }
+
+    Some(crane.tops())
+}
+
+pub fn part_one(input: &str) -> Option<String> {
+    solve(input, Mode::OneAtATime)
+}
+
+pub fn part_two(input: &str) -> Option<String> {
+    solve(input, Mode::Multiple)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_initial() {
+        let input = "    [D]    \n\
+                     [N] [C]    \n\
+                     [Z] [M] [P]\n\
+                      1   2   3 ";
+        let crane = parse_initial(input.lines());
+        let stacks = crane.stacks.iter().map(|s| s.crates.join("")).collect_vec();
+        assert_eq!(stacks, vec!["ZN", "MCD", "P"]);
+        assert_eq!(crane.ids, vec![1, 2, 3]);
+
+        let example = crate::read_file("examples", 5);
+        let drawing = example.split("\n\n").next().unwrap();
+        assert_eq!(Crane::from_drawing(drawing).to_string(), drawing);
+    }
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 5);
+        assert_eq!(part_one(&input), Some("CMZ".to_string()));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 5);
+        assert_eq!(part_two(&input), Some("MCD".to_string()));
+    }
+
+    #[test]
+    fn test_wide_stacks() {
+        let drawing = "[AB]          \n\
+                       [CD] [EF]     \n\
+                       \x207    10   12 ";
+        let mut crane = Crane::from_drawing(drawing);
+        assert_eq!(crane.ids, vec![7, 10, 12]);
+        assert_eq!(crane.to_string(), drawing);
+
+        crane.apply(
+            Move::from_line("move 2 from 7 to 12").unwrap(),
+            Mode::OneAtATime,
+        );
+        assert_eq!(crane.tops(), "EFCD");
+        crane.apply(
+            Move::from_line("move 2 from 12 to 10").unwrap(),
+            Mode::Multiple,
+        );
+        assert_eq!(crane.tops(), "CD");
+        assert_eq!(
+            crane.to_string(),
+            "     [CD]     \n     [AB]     \n     [EF]     \n 7    10   12 "
+        );
+    }
+}
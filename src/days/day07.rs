@@ -0,0 +1,272 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::ops::RangeBounds;
+
+#[derive(Debug)]
+enum Kind {
+    Dir { children: BTreeMap<String, usize> },
+    File { size: u64 },
+}
+
+#[derive(Debug)]
+struct Node {
+    name: String,
+    parent: usize,
+    kind: Kind,
+}
+
+/// The directory tree rebuilt from a terminal session. The root is the first node and
+/// its own parent.
+#[derive(Debug)]
+pub struct FileSystem {
+    nodes: Vec<Node>,
+}
+
+impl FileSystem {
+    pub fn from_session(input: &str) -> FileSystem {
+        let mut fs = FileSystem {
+            nodes: vec![Node {
+                name: String::new(),
+                parent: 0,
+                kind: Kind::Dir {
+                    children: BTreeMap::new(),
+                },
+            }],
+        };
+        let mut cwd = 0;
+
+        for line in input.lines() {
+            match line.split(' ').collect::<Vec<_>>()[..] {
+                ["$", "ls"] => {}
+                ["$", "cd", path] => cwd = fs.cd(cwd, path),
+                ["dir", name] => {
+                    fs.dir(cwd, name);
+                }
+                [size, name] => {
+                    let size = size.parse().expect("Expected a file size!");
+                    fs.add(cwd, name, Kind::File { size });
+                }
+                _ => panic!("Unexpected line {:?}", line),
+            }
+        }
+
+        fs
+    }
+
+    fn children(&self, id: usize) -> Option<&BTreeMap<String, usize>> {
+        match &self.nodes[id].kind {
+            Kind::Dir { children } => Some(children),
+            Kind::File { .. } => None,
+        }
+    }
+
+    /// Adds a node to the directory `parent`, replacing a file listed before under the same name.
+    fn add(&mut self, parent: usize, name: &str, kind: Kind) -> usize {
+        if let Some(&id) = self.children(parent).and_then(|c| c.get(name)) {
+            if let (Kind::File { size }, Kind::File { size: new }) =
+                (&mut self.nodes[id].kind, kind)
+            {
+                *size = new;
+            }
+            return id;
+        }
+
+        let id = self.nodes.len();
+        self.nodes.push(Node {
+            name: name.to_owned(),
+            parent,
+            kind,
+        });
+        let Kind::Dir { children } = &mut self.nodes[parent].kind else {
+            panic!("{} is not a directory", self.nodes[parent].name);
+        };
+        children.insert(name.to_owned(), id);
+
+        id
+    }
+
+    fn dir(&mut self, parent: usize, name: &str) -> usize {
+        let kind = Kind::Dir {
+            children: BTreeMap::new(),
+        };
+        self.add(parent, name, kind)
+    }
+
+    /// Follows an absolute or relative path, creating directories that were not listed yet.
+    fn cd(&mut self, cwd: usize, path: &str) -> usize {
+        let start = if path.starts_with('/') { 0 } else { cwd };
+
+        path.split('/')
+            .filter(|part| !part.is_empty() && *part != ".")
+            .fold(start, |dir, part| match part {
+                ".." => self.nodes[dir].parent,
+                name => self.dir(dir, name),
+            })
+    }
+
+    pub fn lookup(&self, path: &str) -> Option<usize> {
+        path.split('/')
+            .filter(|part| !part.is_empty())
+            .try_fold(0, |dir, name| self.children(dir)?.get(name).copied())
+    }
+
+    pub fn path(&self, id: usize) -> String {
+        if id == 0 {
+            return "/".to_owned();
+        }
+
+        let mut parts = Vec::new();
+        let mut id = id;
+        while id != 0 {
+            parts.push(self.nodes[id].name.as_str());
+            id = self.nodes[id].parent;
+        }
+        parts
+            .iter()
+            .rev()
+            .fold(String::new(), |path, part| path + "/" + part)
+    }
+
+    /// The total size of every node, including everything below it.
+    fn sizes(&self) -> Vec<u64> {
+        let mut sizes = vec![0; self.nodes.len()];
+        // children are always added after their parent.
+        for (id, node) in self.nodes.iter().enumerate().rev() {
+            if let Kind::File { size } = node.kind {
+                sizes[id] = size;
+            }
+            if id != 0 {
+                sizes[node.parent] += sizes[id];
+            }
+        }
+        sizes
+    }
+
+    /// The path and total size of every directory, like `du`.
+    pub fn du(&self) -> Vec<(String, u64)> {
+        let sizes = self.sizes();
+        self.dirs(0)
+            .into_iter()
+            .map(|id| (self.path(id), sizes[id]))
+            .collect()
+    }
+
+    /// The directories whose total size is within `sizes`.
+    pub fn find(&self, sizes: impl RangeBounds<u64>) -> Vec<(String, u64)> {
+        self.du()
+            .into_iter()
+            .filter(|(_, size)| sizes.contains(size))
+            .collect()
+    }
+
+    /// `id` and every directory below it, parents first.
+    fn dirs(&self, id: usize) -> Vec<usize> {
+        let mut dirs = vec![id];
+        for &child in self.children(id).into_iter().flat_map(|c| c.values()) {
+            if self.children(child).is_some() {
+                dirs.extend(self.dirs(child));
+            }
+        }
+        dirs
+    }
+
+    fn render(&self, f: &mut std::fmt::Formatter<'_>, id: usize, depth: usize) -> std::fmt::Result {
+        let node = &self.nodes[id];
+        let name = if id == 0 { "/" } else { &node.name };
+        write!(f, "{:indent$}- {} ", "", name, indent = depth * 2)?;
+
+        match &node.kind {
+            Kind::File { size } => writeln!(f, "(file, size={})", size),
+            Kind::Dir { children } => {
+                writeln!(f, "(dir)")?;
+                for &child in children.values() {
+                    self.render(f, child, depth + 1)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Display for FileSystem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.render(f, 0, 0)
+    }
+}
+
+pub fn both_parts(input: &str) -> (Option<u64>, Option<u64>) {
+    let fs = FileSystem::from_session(input);
+    let small = fs.find(..=100_000).iter().map(|(_, size)| size).sum();
+
+    let total = fs.du()[0].1;
+    // With enough space free already, deleting even the smallest directory will do.
+    let missing = 30_000_000_u64.saturating_sub(70_000_000_u64.saturating_sub(total));
+    let delete = fs.find(missing..).into_iter().map(|(_, size)| size).min();
+
+    (Some(small), delete)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 7);
+        assert_eq!(both_parts(&input).0, Some(95437));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 7);
+        assert_eq!(both_parts(&input).1, Some(24933642));
+    }
+
+    #[test]
+    fn test_enough_space() {
+        let session = "$ cd /\n$ ls\ndir a\n10 b\n$ cd a\n$ ls\n5 c\n";
+        assert_eq!(both_parts(session), (Some(20), Some(5)));
+    }
+
+    #[test]
+    fn test_queries() {
+        let fs = FileSystem::from_session(&crate::read_file("examples", 7));
+        assert_eq!(
+            fs.du(),
+            [
+                ("/".to_owned(), 48381165),
+                ("/a".to_owned(), 94853),
+                ("/a/e".to_owned(), 584),
+                ("/d".to_owned(), 24933642)
+            ]
+        );
+        assert_eq!(fs.find(..1000), [("/a/e".to_owned(), 584)]);
+        assert_eq!(
+            fs.lookup("/a/e/i").map(|id| fs.path(id)),
+            Some("/a/e/i".to_owned())
+        );
+        assert_eq!(fs.lookup("/a/x"), None);
+
+        let tree = fs.to_string();
+        assert!(
+            tree.starts_with("- / (dir)\n  - a (dir)\n    - e (dir)\n      - i (file, size=584)\n")
+        );
+        assert!(tree.ends_with("    - k (file, size=7214296)\n"));
+    }
+
+    #[test]
+    fn test_revisits() {
+        let session = "$ cd /\n$ ls\ndir a\n10 b\n$ cd a\n$ ls\n5 c\n$ cd /\n$ ls\ndir a\n10 b\n\
+                       $ cd /a\n$ ls\n5 c\n7 d\n$ cd ../x/y\n$ ls\n1 z\n";
+        let fs = FileSystem::from_session(session);
+        assert_eq!(
+            fs.du(),
+            [
+                ("/".to_owned(), 23),
+                ("/a".to_owned(), 12),
+                ("/x".to_owned(), 1),
+                ("/x/y".to_owned(), 1)
+            ]
+        );
+    }
+}
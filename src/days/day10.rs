@@ -0,0 +1,118 @@
+use crate::helpers::ocr::Screen;
+use crate::helpers::vm::{self, Breakpoint, Flow, Registers, Vm};
+
+#[derive(Debug)]
+pub enum Instruction {
+    Noop,
+    Addx(i64),
+}
+
+impl vm::Instruction for Instruction {
+    const REGISTERS: &'static [(&'static str, i64)] = &[("x", 1)];
+
+    fn decode(line: &str) -> Result<Instruction, String> {
+        match line.split_once(' ') {
+            Some(("addx", value)) => value
+                .parse()
+                .map(Self::Addx)
+                .map_err(|_| format!("invalid number {:?}", value)),
+            None if line == "noop" => Ok(Self::Noop),
+            _ => Err(format!("unknown instruction {:?}", line)),
+        }
+    }
+
+    fn cycles(&self) -> u32 {
+        match self {
+            Instruction::Noop => 1,
+            Instruction::Addx(_) => 2,
+        }
+    }
+
+    fn execute(&self, registers: &mut Registers) -> Flow {
+        if let Instruction::Addx(value) = self {
+            registers["x"] += value;
+        }
+        Flow::Next
+    }
+}
+
+struct Crt {
+    screen: String,
+}
+
+impl Crt {
+    fn new() -> Crt {
+        Crt {
+            screen: String::with_capacity(40 * 6 + 6),
+        }
+    }
+
+    fn tick(&mut self, register_x: i64, cycle: u64) {
+        let x = (cycle - 1) as i64 % 40;
+        if x == register_x - 1 || x == register_x || x == register_x + 1 {
+            self.screen.push('#')
+        } else {
+            self.screen.push('.')
+        }
+
+        if cycle.is_multiple_of(40) {
+            self.screen.push('\n')
+        }
+    }
+}
+
+pub fn part_one(input: &str) -> Option<i64> {
+    let mut sum = 0;
+    {
+        let mut cpu = Vm::<Instruction>::load(input).expect("Invalid program!");
+        cpu.on_cycle(|cycle, registers| {
+            if (cycle + 20).is_multiple_of(40) {
+                sum += registers["x"] * cycle as i64;
+            }
+        });
+        cpu.add_breakpoint(Breakpoint::Cycle(220));
+        cpu.run();
+    }
+
+    Some(sum)
+}
+
+pub fn part_two(input: &str) -> Option<Screen> {
+    let mut crt = Crt::new();
+    {
+        let mut cpu = Vm::<Instruction>::load(input).expect("Invalid program!");
+        cpu.on_cycle(|cycle, registers| crt.tick(registers["x"], cycle));
+        cpu.add_breakpoint(Breakpoint::Cycle(240));
+        cpu.run();
+    }
+
+    Some(Screen::new(crt.screen))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RESULT: &str = "##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....\n";
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 10);
+        assert_eq!(part_one(&input), Some(13140));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 10);
+        let part_two = part_two(&input);
+        if let Some(screen) = part_two {
+            assert_eq!(screen.art(), RESULT);
+            assert_eq!(screen.letters(), None);
+        }
+    }
+}
@@ -0,0 +1,276 @@
+use std::collections::{HashMap, VecDeque};
+
+use itertools::Itertools;
+
+use crate::helpers::expr::{BinOp, Expr};
+use crate::params::{self, Param};
+
+pub const PARAMS: &[Param] = &[
+    Param::new("rounds_one", 20),
+    Param::new("relief_one", 3),
+    Param::new("rounds_two", 10_000),
+    Param::new("relief_two", 1),
+];
+
+pub struct Monkey {
+    inspected: u64,
+    items: VecDeque<u64>,
+    /// The right hand side of `new = ...`, in terms of `old`.
+    operation: Expr,
+    test_div_by: u64,
+    test_true: usize,
+    test_false: usize,
+}
+
+fn parse_starting_items(items: &str) -> VecDeque<u64> {
+    let (_, items) = items.split_at("  Starting items: ".len());
+    let items = items.split(", ");
+    items.filter_map(|i| i.parse().ok()).collect()
+}
+
+fn parse_operation(op: &str) -> Expr {
+    let (_, op) = op.split_once("new =").expect("Invalid operation!");
+    let operation = Expr::parse(op).unwrap_or_else(|e| panic!("Invalid operation {:?}: {}", op, e));
+    if let Some(name) = operation
+        .variables()
+        .into_iter()
+        .find(|&name| name != "old")
+    {
+        panic!("Unknown variable {} in operation {:?}", name, op);
+    }
+    operation
+}
+
+fn parse_div(div: &str) -> u64 {
+    let (_, div) = div.split_at("  Test: divisible by ".len());
+    div.parse().expect("Expected a number!")
+}
+
+fn parse_condition(con: &str) -> usize {
+    con.rsplit(' ')
+        .next()
+        .unwrap()
+        .parse()
+        .expect("Expected a number!")
+}
+
+impl Monkey {
+    pub fn parse<'a>(mut lines: impl Iterator<Item = &'a str>) -> Monkey {
+        lines.next();
+        let items = parse_starting_items(lines.next().unwrap());
+        let operation = parse_operation(lines.next().unwrap());
+        let test_div_by = parse_div(lines.next().unwrap());
+        let test_true = parse_condition(lines.next().unwrap());
+        let test_false = parse_condition(lines.next().unwrap());
+
+        Monkey {
+            inspected: 0,
+            items,
+            operation,
+            test_div_by,
+            test_true,
+            test_false,
+        }
+    }
+
+    /// The new worry level of `item` and the monkey it is thrown to.
+    fn inspect(&self, item: u64, relief: u64, modulus: Option<u64>) -> (usize, u64) {
+        let new = self.operation.eval(&[("old", item as i128)]);
+        let mut item = new.unwrap_or_else(|e| panic!("{}", e)) / relief as i128;
+        if let Some(modulus) = modulus {
+            item = item.rem_euclid(modulus as i128);
+        }
+        let item = u64::try_from(item).expect("Worry level out of range!");
+
+        if item.is_multiple_of(self.test_div_by) {
+            (self.test_true, item)
+        } else {
+            (self.test_false, item)
+        }
+    }
+}
+
+pub fn parse_monkeys(input: &str) -> Vec<Monkey> {
+    input
+        .lines()
+        .chunks(7)
+        .into_iter()
+        .map(Monkey::parse)
+        .collect()
+}
+
+/// Keeps worry levels modulo the product of the divisors without relief, which leaves
+/// every test unchanged as long as no operation divides.
+fn modulus(monkeyes: &[Monkey], relief: u64) -> Option<u64> {
+    if relief == 1 && !monkeyes.iter().any(|m| m.operation.uses(BinOp::Div)) {
+        Some(monkeyes.iter().map(|m| m.test_div_by).unique().product())
+    } else {
+        None
+    }
+}
+
+/// Plays `rounds` rounds one monkey at a time, dividing worry levels by `relief` after
+/// each inspection, and returns how many items each monkey inspected.
+pub fn play(monkeyes: &mut [Monkey], rounds: u64, relief: u64) -> Vec<u64> {
+    let modulus = modulus(monkeyes, relief);
+
+    for _round in 0..rounds {
+        for i in 0..monkeyes.len() {
+            let items = std::mem::take(&mut monkeyes[i].items);
+            monkeyes[i].inspected += items.len() as u64;
+
+            for item in items {
+                let (to, item) = monkeyes[i].inspect(item, relief, modulus);
+                monkeyes[to].items.push_back(item);
+            }
+        }
+    }
+
+    monkeyes.iter().map(|m| m.inspected).collect()
+}
+
+/// Counts the same inspections as [`play`] by following each item on its own until its
+/// owner and worry level at the start of a round repeat, then skipping the remaining
+/// laps of that cycle.
+pub fn inspections(monkeyes: &[Monkey], rounds: u64, relief: u64) -> Vec<u64> {
+    let modulus = modulus(monkeyes, relief);
+    let mut inspected = monkeyes.iter().map(|m| m.inspected).collect_vec();
+
+    for (owner, monkey) in monkeyes.iter().enumerate() {
+        for &item in &monkey.items {
+            let counts = follow(monkeyes, (owner, item), rounds, relief, modulus);
+            for (total, count) in inspected.iter_mut().zip(counts) {
+                *total += count;
+            }
+        }
+    }
+
+    inspected
+}
+
+fn follow(
+    monkeyes: &[Monkey],
+    mut state: (usize, u64),
+    rounds: u64,
+    relief: u64,
+    modulus: Option<u64>,
+) -> Vec<u64> {
+    // totals[r] are the inspections made during the first r rounds.
+    let mut totals = vec![vec![0; monkeyes.len()]];
+    let mut seen = HashMap::new();
+
+    for round in 0..rounds {
+        if let Some(start) = seen.insert(state, round) {
+            let length = round - start;
+            crate::debug!(
+                "{:?} repeats every {} rounds from round {}",
+                state,
+                length,
+                start
+            );
+            let (laps, rest) = ((rounds - round) / length, (rounds - round) % length);
+            let (start, round, rest) = (start as usize, round as usize, rest as usize);
+
+            return (0..monkeyes.len())
+                .map(|m| {
+                    let lap = totals[round][m] - totals[start][m];
+                    totals[round][m] + laps * lap + totals[start + rest][m] - totals[start][m]
+                })
+                .collect();
+        }
+
+        // The item stays with the monkeys this round as long as it is thrown forward.
+        let mut counts = totals[totals.len() - 1].clone();
+        let (mut owner, mut item) = state;
+        loop {
+            counts[owner] += 1;
+            let (to, worry) = monkeyes[owner].inspect(item, relief, modulus);
+            let forward = to > owner;
+            (owner, item) = (to, worry);
+            if !forward {
+                break;
+            }
+        }
+
+        state = (owner, item);
+        totals.push(counts);
+    }
+
+    totals.pop().unwrap()
+}
+
+pub fn solve(input: &str, rounds: u64, relief: u64) -> Option<u128> {
+    let monkeyes = parse_monkeys(input);
+
+    let (one, two) = inspections(&monkeyes, rounds, relief)
+        .into_iter()
+        .sorted()
+        .rev()
+        .next_tuple()
+        .unwrap();
+
+    Some(one as u128 * two as u128)
+}
+
+pub fn part_one(input: &str) -> Option<u128> {
+    solve(
+        input,
+        params::get(PARAMS, "rounds_one"),
+        params::get(PARAMS, "relief_one"),
+    )
+}
+
+pub fn part_two(input: &str) -> Option<u128> {
+    solve(
+        input,
+        params::get(PARAMS, "rounds_two"),
+        params::get(PARAMS, "relief_two"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 11);
+        assert_eq!(part_one(&input), Some(10605));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 11);
+        assert_eq!(part_two(&input), Some(2_713_310_158));
+    }
+
+    #[test]
+    fn test_operation() {
+        let operation = parse_operation("  Operation: new = (old + 3) * old - 10 / 2");
+        assert_eq!(operation.eval(&[("old", 2)]), Ok(5 * 2 - 5));
+        assert!(operation.uses(BinOp::Div));
+        assert!(!parse_operation("new = old * old").uses(BinOp::Div));
+    }
+
+    #[test]
+    fn test_rounds() {
+        let input = crate::read_file("examples", 11);
+        assert_eq!(solve(&input, 1_000, 1), Some(5204 * 5192));
+    }
+
+    #[test]
+    fn test_inspections() {
+        let input = crate::read_file("examples", 11);
+        for (rounds, relief) in [(20, 3), (1, 1), (20, 1), (777, 1), (10_000, 1)] {
+            let mut monkeyes = parse_monkeys(&input);
+            let expected = play(&mut monkeyes, rounds, relief);
+            assert_eq!(
+                inspections(&parse_monkeys(&input), rounds, relief),
+                expected
+            );
+        }
+
+        let inspected = inspections(&parse_monkeys(&input), 1_000_000_000_000, 1);
+        assert!(inspected.iter().sum::<u64>() > 1_000_000_000_000);
+    }
+}
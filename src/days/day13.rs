@@ -0,0 +1,485 @@
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use itertools::Itertools;
+use serde_json::Value;
+
+/// Packets nested deeper than this are rejected instead of recursing further.
+pub const MAX_DEPTH: usize = 256;
+
+#[derive(Debug, Clone)]
+pub enum Signal {
+    List { items: Vec<Signal> },
+    Number { value: u32 },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    UnexpectedEnd,
+    Unexpected { position: usize, found: char },
+    Unclosed { position: usize },
+    InvalidNumber { position: usize },
+    TooDeep { position: usize, limit: usize },
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::UnexpectedEnd => write!(f, "unexpected end of packet"),
+            ParseError::Unexpected { position, found } => {
+                write!(f, "unexpected {:?} at {}", found, position)
+            }
+            ParseError::Unclosed { position } => {
+                write!(f, "bracket opened at {} is never closed", position)
+            }
+            ParseError::InvalidNumber { position } => write!(f, "invalid number at {}", position),
+            ParseError::TooDeep { position, limit } => {
+                write!(f, "list at {} is nested deeper than {}", position, limit)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+struct Parser<'a> {
+    input: &'a str,
+    position: usize,
+    max_depth: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.input[self.position..].chars().next()
+    }
+
+    fn unexpected(&self) -> ParseError {
+        match self.peek() {
+            Some(found) => ParseError::Unexpected {
+                position: self.position,
+                found,
+            },
+            None => ParseError::UnexpectedEnd,
+        }
+    }
+
+    fn signal(&mut self, depth: usize) -> Result<Signal, ParseError> {
+        match self.peek() {
+            Some('[') => self.list(depth + 1),
+            Some(c) if c.is_ascii_digit() => self.number(),
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    fn list(&mut self, depth: usize) -> Result<Signal, ParseError> {
+        let start = self.position;
+        if depth > self.max_depth {
+            return Err(ParseError::TooDeep {
+                position: start,
+                limit: self.max_depth,
+            });
+        }
+
+        self.position += 1;
+        let mut items = Vec::new();
+        if self.peek() == Some(']') {
+            self.position += 1;
+            return Ok(Signal::List { items });
+        }
+
+        loop {
+            items.push(self.signal(depth)?);
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some(']') => {
+                    self.position += 1;
+                    return Ok(Signal::List { items });
+                }
+                None => return Err(ParseError::Unclosed { position: start }),
+                _ => return Err(self.unexpected()),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<Signal, ParseError> {
+        let start = self.position;
+        let digits = self.input[start..]
+            .bytes()
+            .take_while(u8::is_ascii_digit)
+            .count();
+        self.position += digits;
+
+        self.input[start..self.position]
+            .parse()
+            .map(|value| Signal::Number { value })
+            .map_err(|_| ParseError::InvalidNumber { position: start })
+    }
+}
+
+impl Signal {
+    /// Parses a packet, rejecting lists nested deeper than `max_depth`.
+    pub fn parse(packet: &str, max_depth: usize) -> Result<Signal, ParseError> {
+        let mut parser = Parser {
+            input: packet,
+            position: 0,
+            max_depth,
+        };
+        let signal = parser.signal(0)?;
+
+        if parser.position < packet.len() {
+            return Err(parser.unexpected());
+        }
+
+        Ok(signal)
+    }
+
+    /// Converts arrays and integers that fit in a `u32`, rejecting arrays nested deeper
+    /// than `max_depth`.
+    pub fn from_json(value: &Value, max_depth: usize) -> Result<Signal, String> {
+        fn convert(value: &Value, depth: usize, max_depth: usize) -> Result<Signal, String> {
+            match value {
+                Value::Array(_) if depth > max_depth => {
+                    Err(format!("array is nested deeper than {}", max_depth))
+                }
+                Value::Array(items) => Ok(Signal::List {
+                    items: items
+                        .iter()
+                        .map(|item| convert(item, depth + 1, max_depth))
+                        .try_collect()?,
+                }),
+                Value::Number(number) => number
+                    .as_u64()
+                    .and_then(|value| u32::try_from(value).ok())
+                    .map(|value| Signal::Number { value })
+                    .ok_or_else(|| format!("{} is not a packet integer", number)),
+                other => Err(format!("{} is not a packet value", other)),
+            }
+        }
+
+        convert(value, 1, max_depth)
+    }
+}
+
+impl FromStr for Signal {
+    type Err = ParseError;
+
+    fn from_str(packet: &str) -> Result<Signal, ParseError> {
+        Signal::parse(packet, MAX_DEPTH)
+    }
+}
+
+impl Display for Signal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Signal::Number { value } => write!(f, "{}", value),
+            Signal::List { items } => write!(f, "[{}]", items.iter().join(",")),
+        }
+    }
+}
+
+impl From<&Signal> for Value {
+    fn from(signal: &Signal) -> Value {
+        match signal {
+            Signal::Number { value } => Value::from(*value),
+            Signal::List { items } => Value::Array(items.iter().map(Value::from).collect()),
+        }
+    }
+}
+
+impl TryFrom<&Value> for Signal {
+    type Error = String;
+
+    fn try_from(value: &Value) -> Result<Signal, String> {
+        Signal::from_json(value, MAX_DEPTH)
+    }
+}
+
+impl PartialEq for Signal {
+    fn eq(&self, other: &Self) -> bool {
+        let com = self.partial_cmp(other);
+        if let Some(result) = com {
+            return result == Ordering::Equal;
+        }
+        false
+    }
+}
+
+impl Ord for Signal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Signal::List { items: self_items }, Signal::List { items: other_items }) => {
+                let mut self_iter = self_items.iter();
+                let mut other_iter = other_items.iter();
+                while let (Some(s), Some(o)) = (self_iter.next(), other_iter.next()) {
+                    let result = s.cmp(o);
+                    if result != Ordering::Equal {
+                        return result;
+                    }
+                }
+
+                self_items.len().cmp(&other_items.len())
+            }
+            (Signal::List { .. }, Signal::Number { value }) => self.cmp(&Signal::List {
+                items: vec![Signal::Number { value: *value }],
+            }),
+            (Signal::Number { value }, Signal::List { .. }) => Signal::List {
+                items: vec![Signal::Number { value: *value }],
+            }
+            .cmp(other),
+            (Signal::Number { value: self_value }, Signal::Number { value: other_value }) => {
+                self_value.cmp(other_value)
+            }
+        }
+    }
+}
+
+impl Eq for Signal {}
+
+impl PartialOrd for Signal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token {
+    Open,
+    Close,
+    Number(u32),
+    End,
+}
+
+/// Reads the tokens of a packet one at a time, wrapping a number in a list when asked to.
+struct Tokens<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    promoted: Option<u32>,
+    /// Closing brackets still owed to numbers that were wrapped in a list.
+    closes: usize,
+}
+
+impl<'a> Tokens<'a> {
+    fn new(bytes: &'a [u8]) -> Tokens<'a> {
+        Tokens {
+            bytes,
+            position: 0,
+            promoted: None,
+            closes: 0,
+        }
+    }
+
+    fn next(&mut self) -> Token {
+        if let Some(value) = self.promoted.take() {
+            return Token::Number(value);
+        }
+        if self.closes > 0 {
+            self.closes -= 1;
+            return Token::Close;
+        }
+
+        while self.bytes.get(self.position) == Some(&b',') {
+            self.position += 1;
+        }
+
+        let Some(&byte) = self.bytes.get(self.position) else {
+            return Token::End;
+        };
+        self.position += 1;
+
+        match byte {
+            b'[' => Token::Open,
+            b']' => Token::Close,
+            b'0'..=b'9' => {
+                let start = self.position - 1;
+                let mut value = u32::from(byte - b'0');
+                while let Some(digit) = self.bytes.get(self.position).filter(|b| b.is_ascii_digit())
+                {
+                    value = value
+                        .checked_mul(10)
+                        .and_then(|value| value.checked_add(u32::from(digit - b'0')))
+                        .unwrap_or_else(|| panic!("Number at {} does not fit in a u32", start));
+                    self.position += 1;
+                }
+                Token::Number(value)
+            }
+            _ => panic!(
+                "Unexpected {:?} at {} in packet",
+                byte as char,
+                self.position - 1
+            ),
+        }
+    }
+
+    /// Makes the number just read come back as the only item of a list whose opening
+    /// bracket was already read.
+    fn promote(&mut self, value: u32) {
+        self.promoted = Some(value);
+        self.closes += 1;
+    }
+}
+
+/// Compares two packets without parsing them, walking both in lockstep.
+///
+/// Panics on bytes that cannot appear in a packet and on numbers that do not fit in a `u32`.
+pub fn compare(left: &[u8], right: &[u8]) -> Ordering {
+    let mut left = Tokens::new(left);
+    let mut right = Tokens::new(right);
+
+    loop {
+        match (left.next(), right.next()) {
+            (Token::End, Token::End) => return Ordering::Equal,
+            (Token::Open, Token::Open) | (Token::Close, Token::Close) => {}
+            (Token::Close, _) | (Token::End, _) => return Ordering::Less,
+            (_, Token::Close) | (_, Token::End) => return Ordering::Greater,
+            (Token::Number(l), Token::Number(r)) if l != r => return l.cmp(&r),
+            (Token::Number(_), Token::Number(_)) => {}
+            (Token::Number(l), Token::Open) => left.promote(l),
+            (Token::Open, Token::Number(r)) => right.promote(r),
+        }
+    }
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    let result = input
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .tuples()
+        .map(|(left, right)| compare(left.as_bytes(), right.as_bytes()))
+        .positions(|o| o == Ordering::Less)
+        .map(|p| p + 1)
+        .sum();
+
+    Some(result)
+}
+
+/// The position of each divider is one more than the number of packets before it.
+pub fn part_two(input: &str) -> Option<usize> {
+    let (mut first, mut second) = (1, 2);
+
+    for line in input.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        if compare(line.as_bytes(), b"[[2]]") == Ordering::Less {
+            first += 1;
+            second += 1;
+        } else if compare(line.as_bytes(), b"[[6]]") == Ordering::Less {
+            second += 1;
+        }
+    }
+
+    Some(first * second)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 13);
+        assert_eq!(part_one(&input), Some(13));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 13);
+        assert_eq!(part_two(&input), Some(140));
+    }
+
+    #[test]
+    fn test_crlf() {
+        let input = crate::read_file("examples", 13).replace('\n', "\r\n");
+        assert_eq!(part_one(&input), Some(13));
+        assert_eq!(part_two(&input), Some(140));
+    }
+
+    #[test]
+    #[should_panic(expected = "Unexpected ' ' at 3 in packet")]
+    fn test_compare_invalid() {
+        compare(b"[1, 2]", b"[1,2]");
+    }
+
+    #[test]
+    #[should_panic(expected = "Number at 1 does not fit in a u32")]
+    fn test_compare_too_large() {
+        compare(b"[99999999999]", b"[1]");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let input = crate::read_file("examples", 13);
+        for line in input.lines().filter(|l| !l.is_empty()) {
+            let signal: Signal = line.parse().unwrap();
+            assert_eq!(signal.to_string(), line);
+
+            let json = Value::from(&signal);
+            assert_eq!(json, serde_json::from_str::<Value>(line).unwrap());
+            // `==` compares packet order, under which `[[1]]` equals `[1]`.
+            assert_eq!(Signal::try_from(&json).unwrap().to_string(), line);
+        }
+
+        let nested = serde_json::json!([[[[]]]]);
+        assert_eq!(
+            Signal::from_json(&nested, 4).unwrap().to_string(),
+            "[[[[]]]]"
+        );
+        assert_eq!(
+            Signal::from_json(&nested, 3),
+            Err("array is nested deeper than 3".to_string())
+        );
+        assert!(Signal::try_from(&serde_json::json!([1, -1])).is_err());
+    }
+
+    #[test]
+    fn test_compare() {
+        let input = crate::read_file("examples", 13);
+        let lines = input.lines().filter(|l| !l.is_empty()).collect_vec();
+
+        for (left, right) in lines.iter().cartesian_product(&lines) {
+            let expected = left.parse::<Signal>().unwrap().cmp(&right.parse().unwrap());
+            assert_eq!(compare(left.as_bytes(), right.as_bytes()), expected);
+        }
+
+        assert_eq!(compare(b"[5]", b"[[[[5]]],1]"), Ordering::Less);
+        assert_eq!(compare(b"[[[[5]]]]", b"[5]"), Ordering::Equal);
+        assert_eq!(compare(b"[10,2]", b"[[10],1]"), Ordering::Greater);
+        assert_eq!(compare(b"[[2]]", b"[[6]]"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        use ParseError::*;
+
+        assert_eq!("[1,[2]".parse::<Signal>(), Err(Unclosed { position: 0 }));
+        assert_eq!(
+            "[1]]".parse::<Signal>(),
+            Err(Unexpected {
+                position: 3,
+                found: ']'
+            })
+        );
+        assert_eq!(
+            "[1,,2]".parse::<Signal>(),
+            Err(Unexpected {
+                position: 3,
+                found: ','
+            })
+        );
+        assert_eq!("[1,".parse::<Signal>(), Err(UnexpectedEnd));
+        assert_eq!(
+            "[99999999999]".parse::<Signal>(),
+            Err(InvalidNumber { position: 1 })
+        );
+        assert_eq!(
+            Signal::parse("[[[[]]]]", 3),
+            Err(TooDeep {
+                position: 3,
+                limit: 3
+            })
+        );
+
+        let deep = "[".repeat(100_000) + &"]".repeat(100_000);
+        assert!(matches!(deep.parse::<Signal>(), Err(TooDeep { .. })));
+        assert!(Signal::try_from(&serde_json::json!([1, "2"])).is_err());
+    }
+}
@@ -0,0 +1,202 @@
+use itertools::Itertools;
+
+use crate::params::{self, Param};
+
+pub const PARAMS: &[Param] = &[
+    Param::new("row", 2_000_000).example(10),
+    Param::new("size", 4_000_000).example(20),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[derive(Debug)]
+pub struct Scan {
+    pub sensor: Point,
+    pub beacon: Point,
+    pub distance: i32,
+}
+
+impl Point {
+    fn from(str: &str) -> Point {
+        let (x, y) = str.split_once(',').unwrap();
+        let (x, y) = (x.parse().unwrap(), y.parse().unwrap());
+
+        Point { x, y }
+    }
+
+    fn manhattan_distance(&self, other: &Point) -> i32 {
+        (self.x.abs_diff(other.x) + self.y.abs_diff(other.y)) as i32
+    }
+}
+
+impl Scan {
+    pub fn from_line(line: &str) -> Scan {
+        let line = line
+            .matches(|c: char| c.is_ascii_digit() || c == ',' || c == ':' || c == '-')
+            .join("");
+        let (sensor, beacon) = line.split_once(':').unwrap();
+        let (sensor, beacon) = (Point::from(sensor), Point::from(beacon));
+
+        let distance = sensor.manhattan_distance(&beacon);
+
+        Scan {
+            sensor,
+            beacon,
+            distance,
+        }
+    }
+
+    fn is_outside(&self, point: &Point) -> bool {
+        self.sensor.manhattan_distance(point) > self.distance
+    }
+
+    /// The columns this sensor covers on row `y`, if any.
+    fn row_interval(&self, y: i32) -> Option<(i32, i32)> {
+        let half = self.distance - self.sensor.y.abs_diff(y) as i32;
+        (half >= 0).then(|| (self.sensor.x - half, self.sensor.x + half))
+    }
+
+    /// The `x + y` and `x - y` constants of the lines just outside this sensor's range.
+    fn boundaries(&self) -> ([i64; 2], [i64; 2]) {
+        let (x, y) = (self.sensor.x as i64, self.sensor.y as i64);
+        let r = self.distance as i64 + 1;
+        ([x + y - r, x + y + r], [x - y - r, x - y + r])
+    }
+}
+
+/// Sorts and merges overlapping or touching intervals.
+fn merge(mut intervals: Vec<(i32, i32)>) -> Vec<(i32, i32)> {
+    intervals.sort_unstable();
+
+    let mut merged: Vec<(i32, i32)> = Vec::with_capacity(intervals.len());
+    for (start, end) in intervals {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 + 1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+pub fn solve_part_one(input: &str, y: i32) -> Option<usize> {
+    let scans = input.lines().map(Scan::from_line).collect_vec();
+
+    let covered = merge(scans.iter().filter_map(|s| s.row_interval(y)).collect());
+    let beacons = scans
+        .iter()
+        .filter(|s| s.beacon.y == y)
+        .map(|s| s.beacon.x)
+        .unique()
+        .filter(|x| covered.iter().any(|(start, end)| start <= x && x <= end))
+        .count();
+
+    let positions: usize = covered
+        .iter()
+        .map(|(start, end)| (end - start + 1) as usize)
+        .sum();
+
+    Some(positions - beacons)
+}
+
+/// The lines that lie just outside of two sensors at once, which is where a single gap
+/// between them has to be.
+fn shared(lines: &[[i64; 2]]) -> Vec<i64> {
+    lines
+        .iter()
+        .map(|l| l[1])
+        .filter(|upper| lines.iter().any(|l| l[0] == *upper))
+        .unique()
+        .collect()
+}
+
+pub fn solve_part_two(input: &str, max_size: u32) -> Option<usize> {
+    let scans = input.lines().map(Scan::from_line).collect_vec();
+    let size = max_size as i64;
+
+    let (sum_lines, difference_lines): (Vec<_>, Vec<_>) =
+        scans.iter().map(Scan::boundaries).unzip();
+
+    let is_gap = |&(x, y): &(i64, i64)| {
+        let point = Point {
+            x: x as i32,
+            y: y as i32,
+        };
+        0 <= x && x <= size && 0 <= y && y <= size && scans.iter().all(|s| s.is_outside(&point))
+    };
+    let crossings = |sums: &[i64], differences: &[i64]| {
+        sums.iter()
+            .cartesian_product(differences)
+            .filter(|(a, b)| (*a - *b) % 2 == 0)
+            .map(|(a, b)| ((a + b) / 2, (a - b) / 2))
+            .find(is_gap)
+    };
+
+    let (sums, differences) = (sum_lines.concat(), difference_lines.concat());
+    let on_edges = || {
+        let corners = [(0, 0), (0, size), (size, 0), (size, size)];
+        let along_sums = sums
+            .iter()
+            .flat_map(|&a| [(0, a), (a, 0), (size, a - size), (a - size, size)]);
+        let along_differences = differences
+            .iter()
+            .flat_map(|&b| [(0, -b), (b, 0), (size, size - b), (b + size, size)]);
+        corners
+            .into_iter()
+            .chain(along_sums)
+            .chain(along_differences)
+            .find(is_gap)
+    };
+
+    // Fall back to every pair of boundary lines if the gap is not squeezed between two
+    // sensors. A gap on the edge of the search area, e.g. in a corner, may only touch a
+    // single line, so then try the corners and where each line meets the edges.
+    let (x, y) = crossings(&shared(&sum_lines), &shared(&difference_lines))
+        .or_else(|| crossings(&sums, &differences))
+        .or_else(on_edges)?;
+
+    Some((x * 4_000_000 + y) as usize)
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    solve_part_one(input, params::get(PARAMS, "row"))
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    solve_part_two(input, params::get(PARAMS, "size"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 15);
+        assert_eq!(part_one(&input), Some(26));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 15);
+        assert_eq!(part_two(&input), Some(56000011));
+    }
+
+    #[test]
+    fn test_gaps_in_row() {
+        let input = "Sensor at x=0, y=0: closest beacon is at x=2, y=0\n\
+                     Sensor at x=10, y=0: closest beacon is at x=10, y=1\n\
+                     Sensor at x=-3, y=5: closest beacon is at x=-3, y=4";
+        // -2..=2 and 9..=11 without the beacon at 2.
+        assert_eq!(solve_part_one(input, 0), Some(7));
+    }
+
+    #[test]
+    fn test_gap_in_corner() {
+        let input = "Sensor at x=20, y=20: closest beacon is at x=20, y=59";
+        assert_eq!(solve_part_two(input, 20), Some(0));
+    }
+}
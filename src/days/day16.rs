@@ -0,0 +1,377 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Display;
+
+use itertools::Itertools;
+
+use crate::Solution;
+
+#[derive(Debug)]
+pub struct Room {
+    pub name: String,
+    pub tunnels: Vec<String>,
+    pub rate: u32,
+}
+
+pub type Rooms = HashMap<String, Room>;
+
+impl Room {
+    pub fn from_line(line: &str) -> Room {
+        let line = line.trim_start_matches("Valve ");
+        let (name, line) = line.split_at(2);
+        let line = line.trim_start_matches(" has flow rate=");
+        let (rate, line) = line.split_once(';').unwrap();
+        let line = line.trim_start_matches(" tunnels lead to valves ");
+        let line = line.trim_start_matches(" tunnel leads to valve ");
+        let tunnels = line.split(", ").map(|t| t.to_owned()).collect_vec();
+
+        Room {
+            name: name.to_owned(),
+            tunnels,
+            rate: rate.parse().unwrap(),
+        }
+    }
+}
+
+pub fn parse_rooms(input: &str) -> Rooms {
+    let lines = input.lines().sorted().collect_vec();
+
+    lines
+        .iter()
+        .map(|l| (l[6..8].to_owned(), Room::from_line(l)))
+        .collect()
+}
+
+/// The most valves worth opening. The searches keep tables over every subset of them,
+/// which take a few hundred MB at this size.
+pub const MAX_VALVES: usize = 20;
+
+/// The valves worth opening, interned to small ids so a set of them fits in a `u64`.
+pub struct Network {
+    pub names: Vec<String>,
+    pub rates: Vec<u32>,
+    /// Minutes to walk from one valve to another and open it. The start is the last row.
+    distances: Vec<Vec<u32>>,
+}
+
+impl Network {
+    pub fn new(rooms: &Rooms, start: &str) -> Network {
+        let ids: HashMap<&str, usize> = rooms
+            .keys()
+            .sorted()
+            .enumerate()
+            .map(|(id, name)| (name.as_str(), id))
+            .collect();
+        let mut tunnels = vec![Vec::new(); ids.len()];
+        for room in rooms.values() {
+            tunnels[ids[room.name.as_str()]] =
+                room.tunnels.iter().map(|t| ids[t.as_str()]).collect();
+        }
+
+        let mut names = rooms
+            .values()
+            .filter(|room| room.rate > 0)
+            .map(|room| room.name.to_owned())
+            .sorted()
+            .collect_vec();
+        assert!(
+            names.len() <= MAX_VALVES,
+            "{} valves are worth opening, the tables over all their subsets only fit {}",
+            names.len(),
+            MAX_VALVES
+        );
+        let rates = names.iter().map(|name| rooms[name].rate).collect();
+        names.push(start.to_owned());
+
+        let distances = names
+            .iter()
+            .map(|from| {
+                let steps = steps_from(&tunnels, ids[from.as_str()]);
+                names
+                    .iter()
+                    .map(|to| steps[ids[to.as_str()]].saturating_add(1))
+                    .collect()
+            })
+            .collect();
+
+        Network {
+            names,
+            rates,
+            distances,
+        }
+    }
+
+    fn valves(&self) -> usize {
+        self.rates.len()
+    }
+
+    fn start(&self) -> usize {
+        self.valves()
+    }
+
+    /// Walks through every order of opening the valves in `allowed` within `minutes`.
+    fn walk(&self, allowed: u64, minutes: u32) -> Walk<'_> {
+        let mut walk = Walk::new(self, allowed);
+        walk.visit(self.start(), minutes, 0, 0);
+        walk
+    }
+}
+
+/// A single actor opening valves one after another.
+struct Walk<'a> {
+    network: &'a Network,
+    allowed: u64,
+    /// The time left and pressure released the last time each valve was reached with each set
+    /// opened, or zeros if it never was.
+    seen: Vec<(u32, u32)>,
+    /// The most pressure released by opening exactly each set of valves.
+    released: Vec<u32>,
+    path: Vec<(usize, u32)>,
+    most: u32,
+    best: Vec<(usize, u32)>,
+}
+
+impl<'a> Walk<'a> {
+    fn new(network: &'a Network, allowed: u64) -> Walk<'a> {
+        let sets = 1 << network.valves();
+        Walk {
+            network,
+            allowed,
+            seen: vec![(0, 0); sets * network.distances.len()],
+            released: vec![0; sets],
+            path: Vec::new(),
+            most: 0,
+            best: Vec::new(),
+        }
+    }
+
+    fn openings(&self, minutes: u32) -> Vec<Opening> {
+        self.best
+            .iter()
+            .map(|&(valve, left)| Opening {
+                valve: self.network.names[valve].to_owned(),
+                minute: minutes - left,
+            })
+            .collect()
+    }
+
+    /// Skips states that an earlier visit reached with as much time left and pressure released.
+    fn visit(&mut self, at: usize, left: u32, opened: u64, pressure: u32) {
+        let state = opened as usize * self.network.distances.len() + at;
+        let (l, p) = self.seen[state];
+        if l >= left && p >= pressure {
+            return;
+        }
+        self.seen[state] = (left, pressure);
+
+        if pressure > self.most {
+            self.most = pressure;
+            self.best = self.path.clone();
+        }
+        self.released[opened as usize] = self.released[opened as usize].max(pressure);
+
+        for next in 0..self.network.valves() {
+            let distance = self.network.distances[at][next];
+            if opened & 1 << next != 0 || self.allowed & 1 << next == 0 || distance >= left {
+                continue;
+            }
+            let left = left - distance;
+            let pressure = pressure + self.network.rates[next] * left;
+            self.path.push((next, left));
+            self.visit(next, left, opened | 1 << next, pressure);
+            self.path.pop();
+        }
+    }
+}
+
+fn steps_from(tunnels: &[Vec<usize>], from: usize) -> Vec<u32> {
+    let mut steps = vec![u32::MAX; tunnels.len()];
+    let mut queue = VecDeque::new();
+    steps[from] = 0;
+    queue.push_back(from);
+
+    while let Some(room) = queue.pop_front() {
+        for &next in &tunnels[room] {
+            if steps[next] == u32::MAX {
+                steps[next] = steps[room] + 1;
+                queue.push_back(next);
+            }
+        }
+    }
+
+    steps
+}
+
+/// Iterates over every subset of `mask`, including the empty set and `mask` itself.
+fn subsets(mask: u64) -> impl Iterator<Item = u64> {
+    let mut next = Some(mask);
+    std::iter::from_fn(move || {
+        let subset = next?;
+        next = (subset != 0).then(|| (subset - 1) & mask);
+        Some(subset)
+    })
+}
+
+/// The best way to give one actor a subset of `mask` and the rest of `mask` to the
+/// actors already accounted for in `others`.
+fn split(single: &[u32], others: &[u32], mask: u64) -> (u32, u64) {
+    subsets(mask)
+        .map(|subset| {
+            (
+                single[subset as usize] + others[(mask ^ subset) as usize],
+                subset,
+            )
+        })
+        .max()
+        .unwrap()
+}
+
+/// A valve being opened by an actor, releasing pressure from the end of `minute` on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Opening {
+    pub valve: String,
+    pub minute: u32,
+}
+
+/// The valves opened by each actor, in the order they open them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Schedule {
+    pub pressure: u32,
+    pub actors: Vec<Vec<Opening>>,
+}
+
+impl Display for Schedule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} pressure released:", self.pressure)?;
+        for (i, openings) in self.actors.iter().enumerate() {
+            let openings = openings
+                .iter()
+                .map(|o| format!("{} at minute {}", o.valve, o.minute))
+                .join(", ");
+            write!(f, "\n  actor {}: {}", i + 1, openings)?;
+        }
+        Ok(())
+    }
+}
+
+/// The schedule that releases the most pressure when `actors` start together and
+/// work for `minutes`, each opening a disjoint set of valves.
+pub fn best_schedule(network: &Network, actors: usize, minutes: u32) -> Schedule {
+    if actors == 0 {
+        return Schedule::default();
+    }
+
+    let walk = network.walk((1 << network.valves()) - 1, minutes);
+    let schedule = if actors == 1 {
+        Schedule {
+            pressure: walk.most,
+            actors: vec![walk.openings(minutes)],
+        }
+    } else {
+        share(network, &walk.released, actors, minutes)
+    };
+
+    crate::info!("{}", schedule);
+
+    schedule
+}
+
+/// Splits the valves between the actors given what one actor releases with each set.
+fn share(network: &Network, single: &[u32], actors: usize, minutes: u32) -> Schedule {
+    let all = (single.len() - 1) as u64;
+
+    // tables[n][mask] is the most n + 1 actors can release sharing the valves in mask.
+    let mut tables = vec![single.to_vec()];
+    for mask in 0..single.len() {
+        for valve in 0..network.valves() {
+            if mask & 1 << valve != 0 {
+                tables[0][mask] = tables[0][mask].max(tables[0][mask ^ 1 << valve]);
+            }
+        }
+    }
+    for n in 1..actors - 1 {
+        let table = (0..=all)
+            .map(|mask| split(single, &tables[n - 1], mask).0)
+            .collect();
+        tables.push(table);
+    }
+
+    let none = vec![0; single.len()];
+    let mut mask = all;
+    let mut schedule = Schedule::default();
+    for n in (0..actors).rev() {
+        let others = n.checked_sub(1).map_or(&none, |n| &tables[n]);
+        let (pressure, subset) = split(single, others, mask);
+        if n == actors - 1 {
+            schedule.pressure = pressure;
+        }
+        schedule
+            .actors
+            .push(network.walk(subset, minutes).openings(minutes));
+        mask ^= subset;
+    }
+
+    schedule
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = Network;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Self::Input {
+        Network::new(&parse_rooms(input), "AA")
+    }
+
+    fn part_one(network: &Self::Input) -> Option<u32> {
+        Some(best_schedule(network, 1, 30).pressure)
+    }
+
+    fn part_two(network: &Self::Input) -> Option<u32> {
+        Some(best_schedule(network, 2, 26).pressure)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 16);
+        assert_eq!(Day16::part_one(&Day16::parse(&input)), Some(1651));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 16);
+        assert_eq!(Day16::part_two(&Day16::parse(&input)), Some(1707));
+    }
+
+    #[test]
+    fn test_schedule() {
+        let network = Day16::parse(&crate::read_file("examples", 16));
+        let rates: HashMap<_, _> = network.names.iter().zip(&network.rates).collect();
+
+        for actors in 1..=3 {
+            let schedule = best_schedule(&network, actors, 20);
+            assert_eq!(schedule.actors.len(), actors);
+
+            let released = schedule
+                .actors
+                .iter()
+                .flatten()
+                .map(|o| *rates[&o.valve] * (20 - o.minute))
+                .sum::<u32>();
+            assert_eq!(released, schedule.pressure);
+        }
+
+        let schedule = best_schedule(&network, 1, 30);
+        let valves = schedule.actors[0]
+            .iter()
+            .map(|o| o.valve.as_str())
+            .collect_vec();
+        assert_eq!(valves, ["DD", "BB", "JJ", "HH", "EE", "CC"]);
+    }
+}
@@ -0,0 +1,355 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use itertools::Itertools;
+
+use crate::params::{self, Param};
+
+pub const PARAMS: &[Param] = &[
+    Param::new("rocks_one", 2022),
+    Param::new("rocks_two", 1_000_000_000_000),
+    Param::new("width", 7),
+];
+
+/// The rocks of the puzzle in the order they fall, drawn top row first.
+pub const SHAPES: &str = "####
+
+.#.
+###
+.#.
+
+..#
+..#
+###
+
+#
+#
+#
+#
+
+##
+##";
+
+/// One row of the chamber, where bit `x` is set if column `x` is taken.
+type Row = u8;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Wind {
+    Left,
+    Right,
+}
+
+impl Wind {
+    pub fn from_char(char: char) -> Option<Wind> {
+        match char {
+            '>' => Some(Wind::Right),
+            '<' => Some(Wind::Left),
+            _ => None,
+        }
+    }
+
+    pub fn parse_all(text: &str) -> Vec<Wind> {
+        text.chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| Wind::from_char(c).unwrap_or_else(|| panic!("Invalid jet {:?}", c)))
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Shape {
+    /// Bottom row first, with the leftmost column in bit 0.
+    rows: Vec<Row>,
+    width: u32,
+}
+
+impl Shape {
+    /// Parses a drawing of `#` and `.`, top row first.
+    pub fn parse(drawing: &str) -> Shape {
+        let lines = drawing.lines().map(str::trim).filter(|l| !l.is_empty());
+        let rows = lines
+            .rev()
+            .map(|line| {
+                assert!(line.len() <= Row::BITS as usize, "{:?} is too wide", line);
+                line.chars()
+                    .enumerate()
+                    .map(|(x, c)| match c {
+                        '#' => 1 << x,
+                        '.' => 0,
+                        _ => panic!("Unexpected {:?} in shape", c),
+                    })
+                    .fold(0, |row: Row, bit| row | bit)
+            })
+            .collect_vec();
+        assert!(
+            rows.iter().all(|&row| row != 0),
+            "Shapes cannot have empty rows"
+        );
+
+        let width = rows.iter().map(|row| Row::BITS - row.leading_zeros()).max();
+        Shape {
+            width: width.expect("Shapes cannot be empty"),
+            rows,
+        }
+    }
+
+    /// Parses drawings separated by empty lines.
+    pub fn parse_all(text: &str) -> Vec<Shape> {
+        text.split("\n\n").map(Shape::parse).collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Rules {
+    pub width: u32,
+    pub shapes: Vec<Shape>,
+    pub winds: Vec<Wind>,
+}
+
+impl Rules {
+    pub fn new(width: u32, shapes: Vec<Shape>, winds: Vec<Wind>) -> Rules {
+        assert!(width <= Row::BITS, "The chamber is wider than a row");
+        assert!(
+            shapes.iter().all(|s| s.width <= width),
+            "A shape is wider than the chamber"
+        );
+        assert!(
+            !shapes.is_empty() && !winds.is_empty(),
+            "Nothing to simulate"
+        );
+
+        Rules {
+            width,
+            shapes,
+            winds,
+        }
+    }
+}
+
+/// The settled rocks. Rows that no rock can reach anymore are dropped from the bottom.
+#[derive(Clone, Debug)]
+pub struct Chamber {
+    width: u32,
+    rows: Vec<Row>,
+    trimmed: u64,
+}
+
+impl Chamber {
+    pub fn new(width: u32) -> Chamber {
+        Chamber {
+            width,
+            rows: Vec::new(),
+            trimmed: 0,
+        }
+    }
+
+    pub fn height(&self) -> u64 {
+        self.trimmed + self.rows.len() as u64
+    }
+
+    /// Whether `shape` fits with its bottom left corner at `x` and row `y` of `rows`.
+    fn fits(&self, shape: &Shape, x: u32, y: usize) -> bool {
+        x + shape.width <= self.width
+            && shape.rows.iter().enumerate().all(|(i, row)| {
+                let taken = self.rows.get(y + i).copied().unwrap_or(0);
+                taken & (row << x) == 0
+            })
+    }
+
+    /// Drops `shape` from its starting position, pushed by `winds`, until it settles.
+    pub fn drop(&mut self, shape: &Shape, winds: &[Wind], wind: &mut usize) {
+        // Two columns from the left wall, as far as narrower chambers allow.
+        let mut x = 2.min(self.width - shape.width);
+        let mut y = self.rows.len() + 3;
+
+        loop {
+            let pushed = match winds[*wind] {
+                Wind::Left => x.checked_sub(1),
+                Wind::Right => Some(x + 1),
+            };
+            *wind = (*wind + 1) % winds.len();
+            if let Some(pushed) = pushed.filter(|&p| self.fits(shape, p, y)) {
+                x = pushed;
+            }
+
+            if y == 0 || !self.fits(shape, x, y - 1) {
+                break;
+            }
+            y -= 1;
+        }
+
+        let top = y + shape.rows.len();
+        if self.rows.len() < top {
+            self.rows.resize(top, 0);
+        }
+        for (i, row) in shape.rows.iter().enumerate() {
+            self.rows[y + i] |= row << x;
+        }
+    }
+
+    /// The lowest row any of `shapes` can still come to rest on, moving only left, right
+    /// and down from above the tower.
+    fn lowest_reachable(&self, shapes: &[Shape]) -> usize {
+        let (top, width) = (self.rows.len(), self.width as usize);
+        let mut lowest = top;
+
+        for shape in shapes {
+            let mut seen = vec![false; (top + 1) * width];
+            let mut queue = (0..=self.width - shape.width)
+                .map(|x| (x, top))
+                .collect_vec();
+            while let Some((x, y)) = queue.pop() {
+                if std::mem::replace(&mut seen[y * width + x as usize], true) {
+                    continue;
+                }
+                lowest = lowest.min(y);
+
+                let moves = [
+                    x.checked_sub(1).map(|x| (x, y)),
+                    Some((x + 1, y)),
+                    y.checked_sub(1).map(|y| (x, y)),
+                ];
+                queue.extend(
+                    moves
+                        .into_iter()
+                        .flatten()
+                        .filter(|&(x, y)| self.fits(shape, x, y)),
+                );
+            }
+        }
+
+        lowest
+    }
+
+    /// Drops the rows below those that `shapes` can still reach, keeping the row they would
+    /// rest on.
+    pub fn trim(&mut self, shapes: &[Shape]) {
+        let unreachable = self.lowest_reachable(shapes).saturating_sub(1);
+        self.rows.drain(..unreachable);
+        self.trimmed += unreachable as u64;
+    }
+}
+
+/// Draws the chamber the way the puzzle does, with `~` below once rows were dropped.
+impl Display for Chamber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows.iter().rev() {
+            let cells: String = (0..self.width)
+                .map(|x| if row & (1 << x) != 0 { '#' } else { '.' })
+                .collect();
+            writeln!(f, "|{}|", cells)?;
+        }
+
+        let floor = if self.trimmed == 0 { '-' } else { '~' };
+        write!(f, "+{}+", floor.to_string().repeat(self.width as usize))
+    }
+}
+
+/// The height of the tower after `rocks` rocks. Once the next shape, the next jet and the
+/// reachable rows repeat, the rocks in between are skipped a lap at a time.
+pub fn simulate(rules: &Rules, rocks: u64) -> u64 {
+    let mut chamber = Chamber::new(rules.width);
+    let mut wind = 0;
+    let mut seen = HashMap::new();
+    let mut skipped = None;
+
+    let mut rock = 0;
+    while rock < rocks {
+        let shape = (rock % rules.shapes.len() as u64) as usize;
+        chamber.drop(&rules.shapes[shape], &rules.winds, &mut wind);
+        chamber.trim(&rules.shapes);
+        rock += 1;
+
+        if skipped.is_some() {
+            continue;
+        }
+        let state = (shape, wind, chamber.rows.clone());
+        if let Some((start, height)) = seen.insert(state, (rock, chamber.height())) {
+            let (length, growth) = (rock - start, chamber.height() - height);
+            crate::debug!("{} rocks add {} rows from rock {}", length, growth, start);
+
+            let laps = (rocks - rock) / length;
+            rock += laps * length;
+            skipped = Some(laps * growth);
+        }
+    }
+    crate::trace!("{}", chamber);
+
+    chamber.height() + skipped.unwrap_or(0)
+}
+
+fn rules(input: &str) -> Rules {
+    Rules::new(
+        params::get(PARAMS, "width"),
+        Shape::parse_all(SHAPES),
+        Wind::parse_all(input),
+    )
+}
+
+pub fn part_one(input: &str) -> Option<u64> {
+    Some(simulate(&rules(input), params::get(PARAMS, "rocks_one")))
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    Some(simulate(&rules(input), params::get(PARAMS, "rocks_two")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 17);
+        assert_eq!(part_one(&input), Some(3068));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 17);
+        assert_eq!(part_two(&input), Some(1_514_285_714_288));
+    }
+
+    #[test]
+    fn test_first_rocks() {
+        let rules = rules(&crate::read_file("examples", 17));
+        let mut chamber = Chamber::new(rules.width);
+        let mut wind = 0;
+        for shape in &rules.shapes[..3] {
+            chamber.drop(shape, &rules.winds, &mut wind);
+        }
+
+        assert_eq!(
+            chamber.to_string(),
+            "|..#....|\n|..#....|\n|####...|\n|..###..|\n|...#...|\n|..####.|\n+-------+"
+        );
+        for rocks in [1, 10, 100, 3000] {
+            let mut chamber = Chamber::new(rules.width);
+            let mut wind = 0;
+            for rock in 0..rocks {
+                chamber.drop(&rules.shapes[rock % 5], &rules.winds, &mut wind);
+            }
+            assert_eq!(simulate(&rules, rocks as u64), chamber.height());
+        }
+    }
+
+    #[test]
+    fn test_other_rules() {
+        let shapes = Shape::parse_all("##\n\n.#\n##");
+        assert_eq!(shapes[1].rows, [0b11, 0b10]);
+
+        // Bars as wide as the chamber stack without gaps.
+        let rules = Rules::new(2, shapes[..1].to_vec(), Wind::parse_all("<>"));
+        assert_eq!(simulate(&rules, 1_000_000_000_000), 1_000_000_000_000);
+
+        let rules = Rules::new(5, shapes, Wind::parse_all(">><<<\n"));
+        let mut chamber = Chamber::new(rules.width);
+        let mut wind = 0;
+        for rock in 0..5_000 {
+            chamber.drop(&rules.shapes[rock % 2], &rules.winds, &mut wind);
+            chamber.trim(&rules.shapes);
+            assert!(chamber.rows.len() < 100);
+        }
+        assert_eq!(simulate(&rules, 5_000), chamber.height());
+    }
+}
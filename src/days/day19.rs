@@ -0,0 +1,489 @@
+use std::fmt::Display;
+
+use itertools::Itertools;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::helpers::par_map;
+use crate::Solution;
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct Blueprint {
+    pub number: u32,
+    pub ore_robot_cost: u32,
+    pub clay_robot_cost: u32,
+    pub obsidian_robot_cost: (u32, u32),
+    pub geode_robot_cost: (u32, u32),
+}
+
+impl Blueprint {
+    pub fn from_line(line: &str) -> Blueprint {
+        lazy_static! {
+            static ref RE: Regex = Regex::new("Blueprint (\\d*): Each ore robot costs (\\d*) ore. Each clay robot costs (\\d*) ore. Each obsidian robot costs (\\d*) ore and (\\d*) clay. Each geode robot costs (\\d*) ore and (\\d*) obsidian.").unwrap();
+        }
+
+        let captures = RE.captures(line).unwrap();
+        let numbers = captures
+            .iter()
+            .skip(1)
+            .map(|c| c.unwrap().as_str().parse().unwrap())
+            .collect_vec();
+
+        Blueprint {
+            number: numbers[0],
+            ore_robot_cost: numbers[1],
+            clay_robot_cost: numbers[2],
+            obsidian_robot_cost: (numbers[3], numbers[4]),
+            geode_robot_cost: (numbers[5], numbers[6]),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Ore,
+    Clay,
+    Obsidian,
+    Geode,
+    Wait,
+}
+
+impl Action {
+    fn mask(&self) -> u8 {
+        match self {
+            Action::Ore => 1,
+            Action::Clay => 2,
+            Action::Obsidian => 4,
+            Action::Geode => 8,
+            Action::Wait => 0,
+        }
+    }
+}
+
+impl Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Action::Ore => write!(f, "build ore robot"),
+            Action::Clay => write!(f, "build clay robot"),
+            Action::Obsidian => write!(f, "build obsidian robot"),
+            Action::Geode => write!(f, "build geode robot"),
+            Action::Wait => write!(f, "wait"),
+        }
+    }
+}
+
+/// Resources and robots at the end of a minute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Inventory {
+    pub ore: u32,
+    pub clay: u32,
+    pub obsidian: u32,
+    pub geode: u32,
+    pub ore_robots: u32,
+    pub clay_robots: u32,
+    pub obsidian_robots: u32,
+    pub geode_robots: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub minute: u32,
+    pub action: Action,
+    pub inventory: Inventory,
+}
+
+/// The best schedule found for a blueprint, one step per minute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan {
+    pub blueprint: u32,
+    pub steps: Vec<Step>,
+}
+
+impl Plan {
+    pub fn geodes(&self) -> u32 {
+        self.steps.last().map_or(0, |step| step.inventory.geode)
+    }
+}
+
+impl Display for Plan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Blueprint {} opens {} geodes:",
+            self.blueprint,
+            self.geodes()
+        )?;
+        for Step {
+            minute,
+            action,
+            inventory: i,
+        } in &self.steps
+        {
+            write!(
+                f,
+                "\n{:>3}: {:<20} | {} ore, {} clay, {} obsidian, {} geode | robots: {} ore, {} clay, {} obsidian, {} geode",
+                minute,
+                action.to_string(),
+                i.ore,
+                i.clay,
+                i.obsidian,
+                i.geode,
+                i.ore_robots,
+                i.clay_robots,
+                i.obsidian_robots,
+                i.geode_robots
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct State<'a> {
+    blueprint: &'a Blueprint,
+    ore: u32,
+    clay: u32,
+    obsidian: u32,
+    geode: u32,
+    ore_robots: u32,
+    clay_robots: u32,
+    obsidian_robots: u32,
+    geode_robots: u32,
+    minute: u32,
+}
+
+impl State<'_> {
+    fn new(blueprint: &Blueprint) -> State<'_> {
+        State {
+            blueprint,
+            ore: 0,
+            clay: 0,
+            obsidian: 0,
+            geode: 0,
+            ore_robots: 1,
+            clay_robots: 0,
+            obsidian_robots: 0,
+            geode_robots: 0,
+            minute: 0,
+        }
+    }
+
+    /// All actions that are affordable and useful, most promising first.
+    ///
+    /// A robot is only useful while there are fewer of them than the most of its
+    /// resource that can be spent in a single minute, since only one robot is built per minute.
+    fn get_actions(&self) -> impl Iterator<Item = Action> {
+        let blueprint = self.blueprint;
+        let max_ore_cost = blueprint
+            .ore_robot_cost
+            .max(blueprint.clay_robot_cost)
+            .max(blueprint.obsidian_robot_cost.0)
+            .max(blueprint.geode_robot_cost.0);
+
+        let mut actions = vec![];
+
+        if blueprint.geode_robot_cost.0 <= self.ore && blueprint.geode_robot_cost.1 <= self.obsidian
+        {
+            actions.push(Action::Geode);
+        }
+
+        if blueprint.obsidian_robot_cost.0 <= self.ore
+            && blueprint.obsidian_robot_cost.1 <= self.clay
+            && self.obsidian_robots < blueprint.geode_robot_cost.1
+        {
+            actions.push(Action::Obsidian);
+        }
+
+        if blueprint.clay_robot_cost <= self.ore
+            && self.clay_robots < blueprint.obsidian_robot_cost.1
+        {
+            actions.push(Action::Clay);
+        }
+
+        if blueprint.ore_robot_cost <= self.ore && self.ore_robots < max_ore_cost {
+            actions.push(Action::Ore);
+        }
+
+        actions.push(Action::Wait);
+
+        actions.into_iter()
+    }
+
+    fn apply(&mut self, action: &Action) {
+        self.ore += self.ore_robots;
+        self.clay += self.clay_robots;
+        self.obsidian += self.obsidian_robots;
+        self.geode += self.geode_robots;
+        match action {
+            Action::Ore => {
+                self.ore -= self.blueprint.ore_robot_cost;
+                self.ore_robots += 1;
+            }
+            Action::Clay => {
+                self.ore -= self.blueprint.clay_robot_cost;
+                self.clay_robots += 1;
+            }
+            Action::Obsidian => {
+                self.ore -= self.blueprint.obsidian_robot_cost.0;
+                self.clay -= self.blueprint.obsidian_robot_cost.1;
+                self.obsidian_robots += 1;
+            }
+            Action::Geode => {
+                self.ore -= self.blueprint.geode_robot_cost.0;
+                self.obsidian -= self.blueprint.geode_robot_cost.1;
+                self.geode_robots += 1;
+            }
+            Action::Wait => {}
+        }
+        self.minute += 1;
+    }
+
+    fn unapply(&mut self, action: &Action) {
+        self.minute -= 1;
+        match action {
+            Action::Ore => {
+                self.ore += self.blueprint.ore_robot_cost;
+                self.ore_robots -= 1;
+            }
+            Action::Clay => {
+                self.ore += self.blueprint.clay_robot_cost;
+                self.clay_robots -= 1;
+            }
+            Action::Obsidian => {
+                self.ore += self.blueprint.obsidian_robot_cost.0;
+                self.clay += self.blueprint.obsidian_robot_cost.1;
+                self.obsidian_robots -= 1;
+            }
+            Action::Geode => {
+                self.ore += self.blueprint.geode_robot_cost.0;
+                self.obsidian += self.blueprint.geode_robot_cost.1;
+                self.geode_robots -= 1;
+            }
+            Action::Wait => {}
+        }
+        self.ore -= self.ore_robots;
+        self.clay -= self.clay_robots;
+        self.obsidian -= self.obsidian_robots;
+        self.geode -= self.geode_robots;
+    }
+
+    fn inventory(&self) -> Inventory {
+        Inventory {
+            ore: self.ore,
+            clay: self.clay,
+            obsidian: self.obsidian,
+            geode: self.geode,
+            ore_robots: self.ore_robots,
+            clay_robots: self.clay_robots,
+            obsidian_robots: self.obsidian_robots,
+            geode_robots: self.geode_robots,
+        }
+    }
+
+    /// The geodes opened when ore is free and a clay robot is built every minute,
+    /// with obsidian and geode robots built whenever their other resource allows it.
+    /// No real schedule can do better, which makes this safe to prune with.
+    fn upper_bound(&self, minutes: u32) -> u32 {
+        let (_, clay_cost) = self.blueprint.obsidian_robot_cost;
+        let (_, obsidian_cost) = self.blueprint.geode_robot_cost;
+
+        let (mut clay, mut clay_robots) = (self.clay, self.clay_robots);
+        let (mut obsidian, mut obsidian_robots) = (self.obsidian, self.obsidian_robots);
+        let (mut geode, mut geode_robots) = (self.geode, self.geode_robots);
+
+        for _ in self.minute..minutes {
+            let build_geode = obsidian >= obsidian_cost;
+            let build_obsidian = clay >= clay_cost;
+
+            clay += clay_robots;
+            obsidian += obsidian_robots;
+            geode += geode_robots;
+
+            if build_geode {
+                obsidian -= obsidian_cost;
+                geode_robots += 1;
+            }
+            if build_obsidian {
+                clay -= clay_cost;
+                obsidian_robots += 1;
+            }
+            clay_robots += 1;
+        }
+
+        geode
+    }
+}
+
+struct Search {
+    minutes: u32,
+    path: Vec<Action>,
+    best: Option<(u32, Vec<Action>)>,
+}
+
+impl Search {
+    fn best_geodes(&self) -> u32 {
+        self.best.as_ref().map_or(0, |(geodes, _)| *geodes)
+    }
+
+    /// Depth first branch and bound over the action taken each minute.
+    ///
+    /// `skipped` holds the robots that were affordable when the previous minute was spent
+    /// waiting. Building one of them now is never better than having built it a minute earlier.
+    fn run(&mut self, state: &mut State, skipped: u8) {
+        if state.minute == self.minutes {
+            if self.best.is_none() || state.geode > self.best_geodes() {
+                self.best = Some((state.geode, self.path.clone()));
+            }
+            return;
+        }
+
+        if self.best.is_some() && state.upper_bound(self.minutes) <= self.best_geodes() {
+            return;
+        }
+
+        // a robot built in the last minute never gets to collect anything.
+        let actions = if state.minute + 1 == self.minutes {
+            vec![Action::Wait]
+        } else {
+            state.get_actions().collect_vec()
+        };
+        let affordable = actions.iter().fold(0, |mask, action| mask | action.mask());
+
+        for action in actions {
+            if action.mask() & skipped != 0 {
+                continue;
+            }
+
+            let skipped = if action == Action::Wait {
+                skipped | affordable
+            } else {
+                0
+            };
+
+            state.apply(&action);
+            self.path.push(action);
+            self.run(state, skipped);
+            self.path.pop();
+            state.unapply(&action);
+        }
+    }
+}
+
+/// The schedule that opens the most geodes with `blueprint` in `minutes`.
+pub fn best_plan(blueprint: &Blueprint, minutes: u32) -> Plan {
+    let mut search = Search {
+        minutes,
+        path: Vec::with_capacity(minutes as usize),
+        best: None,
+    };
+    search.run(&mut State::new(blueprint), 0);
+
+    let (_, actions) = search.best.unwrap_or_default();
+    let mut state = State::new(blueprint);
+    let steps = actions
+        .into_iter()
+        .map(|action| {
+            state.apply(&action);
+            Step {
+                minute: state.minute,
+                action,
+                inventory: state.inventory(),
+            }
+        })
+        .collect();
+
+    let plan = Plan {
+        blueprint: blueprint.number,
+        steps,
+    };
+    crate::info!("{}", plan);
+
+    plan
+}
+
+/// The most geodes `blueprint` can open in `minutes`.
+pub fn max_geodes(blueprint: &Blueprint, minutes: u32) -> u32 {
+    best_plan(blueprint, minutes).geodes()
+}
+
+fn all_max_geodes(blueprints: &[Blueprint], minutes: u32) -> Vec<u32> {
+    par_map(blueprints, |blueprint| max_geodes(blueprint, minutes))
+}
+
+/// The sum of the quality levels of all blueprints.
+pub fn quality_levels(blueprints: &[Blueprint], minutes: u32) -> u32 {
+    blueprints
+        .iter()
+        .zip(all_max_geodes(blueprints, minutes))
+        .map(|(blueprint, geodes)| blueprint.number * geodes)
+        .sum()
+}
+
+/// The product of the geodes opened by the first `count` blueprints.
+pub fn geode_product(blueprints: &[Blueprint], minutes: u32, count: usize) -> u32 {
+    let count = count.min(blueprints.len());
+    all_max_geodes(&blueprints[..count], minutes)
+        .into_iter()
+        .product()
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = Vec<Blueprint>;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Self::Input {
+        input.lines().map(Blueprint::from_line).collect()
+    }
+
+    fn part_one(blueprints: &Self::Input) -> Option<u32> {
+        Some(quality_levels(blueprints, 24))
+    }
+
+    fn part_two(blueprints: &Self::Input) -> Option<u32> {
+        Some(geode_product(blueprints, 32, 3))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 19);
+        assert_eq!(Day19::part_one(&Day19::parse(&input)), Some(33));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 19);
+        assert_eq!(Day19::part_two(&Day19::parse(&input)), Some(62 * 56));
+    }
+
+    #[test]
+    fn test_longer_horizon() {
+        let blueprints = Day19::parse(&crate::read_file("examples", 19));
+        assert_eq!(max_geodes(&blueprints[0], 24), 9);
+        assert_eq!(max_geodes(&blueprints[1], 24), 12);
+        assert!(max_geodes(&blueprints[0], 36) > max_geodes(&blueprints[0], 32));
+    }
+
+    #[test]
+    fn test_best_plan() {
+        let blueprints = Day19::parse(&crate::read_file("examples", 19));
+        let plan = best_plan(&blueprints[0], 24);
+
+        assert_eq!(plan.steps.len(), 24);
+        assert_eq!(plan.geodes(), 9);
+
+        let robots = plan
+            .steps
+            .iter()
+            .filter(|step| step.action == Action::Geode)
+            .count();
+        assert_eq!(robots as u32, plan.steps[23].inventory.geode_robots);
+    }
+}
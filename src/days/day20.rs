@@ -0,0 +1,382 @@
+use itertools::Itertools;
+
+pub fn move_new(file: &mut Vec<(usize, i64)>, (index, number): (usize, i64)) {
+    if number == 0 {
+        return;
+    }
+
+    let len = file.len() as i64;
+    let rotate = (number / len) % (len - 1);
+    let mut actual_index = file.iter().position(|&n| n.0 == index).unwrap() as i64;
+
+    if rotate != 0 {
+        let element = file.remove(actual_index as usize);
+
+        if rotate < 0 {
+            file.rotate_right(rotate.unsigned_abs() as usize);
+        } else {
+            file.rotate_left(rotate as usize);
+        }
+
+        file.insert(actual_index as usize, element);
+    }
+
+    let new_position = actual_index + number % len;
+
+    if new_position < actual_index {
+        while actual_index != new_position {
+            file.swap(
+                ((actual_index - 1).rem_euclid(len)) as usize,
+                (actual_index.rem_euclid(len)) as usize,
+            );
+            actual_index -= 1;
+        }
+    } else {
+        while actual_index != new_position {
+            file.swap(
+                ((actual_index).rem_euclid(len)) as usize,
+                ((actual_index + 1).rem_euclid(len)) as usize,
+            );
+            actual_index += 1;
+        }
+    }
+}
+
+fn _move_number(file: &mut [(usize, i64)], (index, number): (usize, i64)) {
+    if number == 0 {
+        return;
+    }
+
+    let len = file.len() as i64;
+
+    let mut actual_index = file.iter().position(|&n| n.0 == index).unwrap() as i64;
+
+    let new_position = actual_index + number;
+
+    if new_position < actual_index {
+        while actual_index != new_position {
+            file.swap(
+                ((actual_index - 1).rem_euclid(len)) as usize,
+                (actual_index.rem_euclid(len)) as usize,
+            );
+            actual_index -= 1;
+        }
+    } else {
+        while actual_index != new_position {
+            file.swap(
+                ((actual_index).rem_euclid(len)) as usize,
+                ((actual_index + 1).rem_euclid(len)) as usize,
+            );
+            actual_index += 1;
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    value: i64,
+    priority: u64,
+    size: usize,
+    left: Option<usize>,
+    right: Option<usize>,
+    parent: Option<usize>,
+}
+
+/// The file as an implicit treap, where a node's key is its position in the file. Nodes keep
+/// their original index so both finding a number and moving it take O(log n).
+#[derive(Debug, Clone)]
+pub struct Mixer {
+    nodes: Vec<Node>,
+    root: Option<usize>,
+}
+
+impl Mixer {
+    pub fn new(numbers: &[i64]) -> Mixer {
+        let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+        let nodes = numbers
+            .iter()
+            .map(|&value| {
+                // xorshift, the priorities only need to look random.
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                Node {
+                    value,
+                    priority: seed,
+                    size: 1,
+                    left: None,
+                    right: None,
+                    parent: None,
+                }
+            })
+            .collect();
+
+        let mut mixer = Mixer { nodes, root: None };
+        for i in 0..numbers.len() {
+            mixer.root = mixer.merge(mixer.root, Some(i));
+        }
+        mixer.set_root();
+        mixer
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    fn size(&self, node: Option<usize>) -> usize {
+        node.map_or(0, |n| self.nodes[n].size)
+    }
+
+    fn update(&mut self, node: usize) {
+        let (left, right) = (self.nodes[node].left, self.nodes[node].right);
+        self.nodes[node].size = 1 + self.size(left) + self.size(right);
+        for child in [left, right].into_iter().flatten() {
+            self.nodes[child].parent = Some(node);
+        }
+    }
+
+    fn set_root(&mut self) {
+        if let Some(root) = self.root {
+            self.nodes[root].parent = None;
+        }
+    }
+
+    fn merge(&mut self, left: Option<usize>, right: Option<usize>) -> Option<usize> {
+        let (Some(l), Some(r)) = (left, right) else {
+            return left.or(right);
+        };
+
+        if self.nodes[l].priority > self.nodes[r].priority {
+            self.nodes[l].right = self.merge(self.nodes[l].right, right);
+            self.update(l);
+            Some(l)
+        } else {
+            self.nodes[r].left = self.merge(left, self.nodes[r].left);
+            self.update(r);
+            Some(r)
+        }
+    }
+
+    /// Splits off the first `count` nodes.
+    fn split(&mut self, node: Option<usize>, count: usize) -> (Option<usize>, Option<usize>) {
+        let Some(n) = node else {
+            return (None, None);
+        };
+
+        let left_size = self.size(self.nodes[n].left);
+        if count <= left_size {
+            let (left, right) = self.split(self.nodes[n].left, count);
+            self.nodes[n].left = right;
+            self.update(n);
+            if let Some(left) = left {
+                self.nodes[left].parent = None;
+            }
+            (left, Some(n))
+        } else {
+            let (left, right) = self.split(self.nodes[n].right, count - left_size - 1);
+            self.nodes[n].right = left;
+            self.update(n);
+            if let Some(right) = right {
+                self.nodes[right].parent = None;
+            }
+            (Some(n), right)
+        }
+    }
+
+    /// The current position of the number that started at `index`.
+    pub fn position(&self, index: usize) -> usize {
+        let mut node = index;
+        let mut position = self.size(self.nodes[node].left);
+        while let Some(parent) = self.nodes[node].parent {
+            if self.nodes[parent].right == Some(node) {
+                position += self.size(self.nodes[parent].left) + 1;
+            }
+            node = parent;
+        }
+        position
+    }
+
+    /// The number at `position`.
+    pub fn get(&self, mut position: usize) -> i64 {
+        let mut node = self.root.expect("The file is empty!");
+        loop {
+            let left_size = self.size(self.nodes[node].left);
+            match position.cmp(&left_size) {
+                std::cmp::Ordering::Less => node = self.nodes[node].left.unwrap(),
+                std::cmp::Ordering::Equal => return self.nodes[node].value,
+                std::cmp::Ordering::Greater => {
+                    position -= left_size + 1;
+                    node = self.nodes[node].right.unwrap();
+                }
+            }
+        }
+    }
+
+    /// Moves the number that started at `index` forward or backward by its value.
+    pub fn mix(&mut self, index: usize) {
+        let len = self.len();
+        if len < 2 {
+            return;
+        }
+
+        let position = self.position(index);
+        let (before, rest) = self.split(self.root, position);
+        let (_, after) = self.split(rest, 1);
+        let without = self.merge(before, after);
+
+        let target = (position as i64 + self.nodes[index].value).rem_euclid(len as i64 - 1);
+        let (before, after) = self.split(without, target as usize);
+        self.nodes[index].parent = None;
+        let before = self.merge(before, Some(index));
+        self.root = self.merge(before, after);
+        self.set_root();
+    }
+
+    pub fn numbers(&self) -> Vec<i64> {
+        (0..self.len()).map(|position| self.get(position)).collect()
+    }
+}
+
+fn grove_coordinates(numbers: &[i64], rounds: usize) -> Option<i64> {
+    let mut mixer = Mixer::new(numbers);
+    for _round in 0..rounds {
+        for index in 0..numbers.len() {
+            mixer.mix(index);
+        }
+    }
+
+    let zero = mixer.position(numbers.iter().position(|&n| n == 0)?);
+    let coordinates = [1000, 2000, 3000].map(|offset| mixer.get((zero + offset) % mixer.len()));
+
+    crate::debug!("len {}", mixer.len());
+    crate::debug!("1000th {}", coordinates[0]);
+    crate::debug!("2000th {}", coordinates[1]);
+    crate::debug!("3000th {}", coordinates[2]);
+
+    Some(coordinates.iter().sum())
+}
+
+pub fn part_one(input: &str) -> Option<i64> {
+    let numbers: Vec<i64> = input.lines().filter_map(|l| l.parse().ok()).collect_vec();
+    grove_coordinates(&numbers, 1)
+}
+
+pub fn part_two(input: &str) -> Option<i64> {
+    let numbers: Vec<i64> = input
+        .lines()
+        .filter_map(|l| l.parse().ok())
+        .map(|n: i64| n * 811589153)
+        .collect_vec();
+    grove_coordinates(&numbers, 10)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 20);
+        assert_eq!(part_one(&input), Some(3));
+    }
+
+    #[test]
+    fn move_number_is_equal_to_faster() {
+        let input = crate::read_file("examples", 20);
+        let order: Vec<i64> = input.lines().filter_map(|l| l.parse().ok()).collect_vec();
+        let mut file = order.clone().into_iter().enumerate().collect_vec();
+        let mut expected = file.clone();
+
+        let mut mixer = Mixer::new(&order);
+
+        for pair in order.into_iter().enumerate() {
+            _move_number(&mut expected, pair);
+            move_new(&mut file, pair);
+            mixer.mix(pair.0);
+
+            assert_eq!(file, expected, "After {} array should be:", pair.1);
+            assert_eq!(
+                starting_with_first(mixer.numbers(), &file),
+                file.iter().map(|n| n.1).collect_vec(),
+                "After {} the mixer should match",
+                pair.1
+            );
+        }
+    }
+
+    /// Rotates `numbers` so it starts with the same number as `file`, as the file is circular.
+    fn starting_with_first(mut numbers: Vec<i64>, file: &[(usize, i64)]) -> Vec<i64> {
+        let first = numbers.iter().position(|&n| n == file[0].1).unwrap();
+        numbers.rotate_left(first);
+        numbers
+    }
+
+    #[test]
+    fn mixer_matches_move_new() {
+        let numbers = [0, 5, -18, 100, -310, 2, 7, 7, -7, 1];
+        let mut file = numbers.into_iter().enumerate().collect_vec();
+        let mut mixer = Mixer::new(&numbers);
+
+        for _round in 0..20 {
+            for pair in numbers.into_iter().enumerate() {
+                move_new(&mut file, pair);
+                mixer.mix(pair.0);
+
+                let zero = file.iter().position(|n| n.1 == 0).unwrap();
+                let expected = (0..file.len()).map(|i| file[(zero + i) % file.len()].1);
+                let zero = mixer.position(0);
+                let actual = (0..mixer.len()).map(|i| mixer.get((zero + i) % mixer.len()));
+                assert!(expected.eq(actual));
+            }
+        }
+    }
+
+    #[test]
+    fn move_number_test_1() {
+        let mut file = vec![(0, 1), (1, 2), (2, 4), (3, 3)];
+        let mut actual = file.clone();
+
+        _move_number(&mut file, (2, 4));
+        move_new(&mut actual, (2, 4));
+        assert_eq!(file, actual);
+    }
+
+    #[test]
+    fn move_test() {
+        let mut file = vec![
+            (0, 0),
+            (1, 5),
+            (2, -18),
+            (3, 100),
+            (4, -310),
+            (5, 2),
+            (6, 7),
+        ];
+        let order = file.iter().clone().map(|f| f.1).enumerate().collect_vec();
+        let mut file_actual = file.clone();
+        for round in 0..49 {
+            for (index, num) in order.iter() {
+                println!("Moving {:?}", (index, num));
+                _move_number(&mut file, (*index, *num));
+                move_new(&mut file_actual, (*index, *num));
+                assert_eq!(
+                    file,
+                    file_actual,
+                    "Failed after round {} after moving {:?}",
+                    round,
+                    (index, num)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 20);
+        assert_eq!(part_two(&input), Some(1623178306));
+    }
+}
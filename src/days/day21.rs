@@ -0,0 +1,422 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use crate::helpers::expr::{BinOp, Expr};
+
+/// The greatest common divisor, or `None` if it is 2^127 and does not fit.
+fn gcd(a: i128, b: i128) -> Option<i128> {
+    let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    i128::try_from(a).ok()
+}
+
+/// An exact fraction, always reduced and with a positive denominator. The arithmetic is
+/// checked and returns `None` once a numerator or denominator no longer fits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rational {
+    numerator: i128,
+    denominator: i128,
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational::integer(0);
+    pub const ONE: Rational = Rational::integer(1);
+
+    pub const fn integer(value: i128) -> Rational {
+        Rational {
+            numerator: value,
+            denominator: 1,
+        }
+    }
+
+    pub fn new(numerator: i128, denominator: i128) -> Option<Rational> {
+        assert!(denominator != 0, "{}/0 is not a number", numerator);
+        let divisor = gcd(numerator, denominator)?.checked_mul(denominator.signum())?;
+        Some(Rational {
+            numerator: numerator.checked_div(divisor)?,
+            denominator: denominator.checked_div(divisor)?,
+        })
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numerator == 0
+    }
+
+    pub fn to_integer(self) -> Option<i128> {
+        (self.denominator == 1).then_some(self.numerator)
+    }
+
+    pub fn checked_add(self, other: Rational) -> Option<Rational> {
+        // Denominators are positive, so their divisor always fits.
+        let divisor = gcd(self.denominator, other.denominator)?;
+        let numerator = self
+            .numerator
+            .checked_mul(other.denominator / divisor)?
+            .checked_add(other.numerator.checked_mul(self.denominator / divisor)?)?;
+        let denominator = (self.denominator / divisor).checked_mul(other.denominator)?;
+        Rational::new(numerator, denominator)
+    }
+
+    pub fn checked_neg(self) -> Option<Rational> {
+        Some(Rational {
+            numerator: self.numerator.checked_neg()?,
+            ..self
+        })
+    }
+
+    pub fn checked_sub(self, other: Rational) -> Option<Rational> {
+        self.checked_add(other.checked_neg()?)
+    }
+
+    pub fn checked_mul(self, other: Rational) -> Option<Rational> {
+        // Cancelling crosswise first keeps the products as small as the result allows.
+        let left = gcd(self.numerator, other.denominator)?;
+        let right = gcd(other.numerator, self.denominator)?;
+        Rational::new(
+            (self.numerator / left).checked_mul(other.numerator / right)?,
+            (self.denominator / right).checked_mul(other.denominator / left)?,
+        )
+    }
+
+    /// Panics if `other` is zero.
+    pub fn checked_div(self, other: Rational) -> Option<Rational> {
+        self.checked_mul(Rational::new(other.denominator, other.numerator)?)
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.denominator {
+            1 => write!(f, "{}", self.numerator),
+            _ => write!(f, "{}/{}", self.numerator, self.denominator),
+        }
+    }
+}
+
+/// `slope * humn + offset`, which is what every monkey yells as long as the equation is linear.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Linear {
+    slope: Rational,
+    offset: Rational,
+}
+
+impl Linear {
+    fn constant(value: Rational) -> Linear {
+        Linear {
+            slope: Rational::ZERO,
+            offset: value,
+        }
+    }
+
+    fn scale(self, factor: Rational) -> Option<Linear> {
+        Some(Linear {
+            slope: self.slope.checked_mul(factor)?,
+            offset: self.offset.checked_mul(factor)?,
+        })
+    }
+
+    fn apply(self, operation: BinOp, other: Linear, monkey: &str) -> Result<Linear, SolveError> {
+        let non_linear = || SolveError::NonLinear {
+            monkey: monkey.to_owned(),
+        };
+
+        let result = match operation {
+            BinOp::Add => self.slope.checked_add(other.slope).and_then(|slope| {
+                Some(Linear {
+                    slope,
+                    offset: self.offset.checked_add(other.offset)?,
+                })
+            }),
+            BinOp::Sub => self.slope.checked_sub(other.slope).and_then(|slope| {
+                Some(Linear {
+                    slope,
+                    offset: self.offset.checked_sub(other.offset)?,
+                })
+            }),
+            BinOp::Mul if other.slope.is_zero() => self.scale(other.offset),
+            BinOp::Mul if self.slope.is_zero() => other.scale(self.offset),
+            BinOp::Mul => return Err(non_linear()),
+            BinOp::Div if !other.slope.is_zero() => return Err(non_linear()),
+            BinOp::Div if other.offset.is_zero() => {
+                return Err(SolveError::DivisionByZero {
+                    monkey: monkey.to_owned(),
+                })
+            }
+            BinOp::Div => Rational::ONE
+                .checked_div(other.offset)
+                .and_then(|factor| self.scale(factor)),
+        };
+
+        result.ok_or_else(|| SolveError::Overflow {
+            monkey: monkey.to_owned(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    UnknownMonkey { name: String },
+    Cycle { monkey: String },
+    DivisionByZero { monkey: String },
+    NonLinear { monkey: String },
+    Overflow { monkey: String },
+    NoSolution,
+    AnySolution,
+    NotIntegral { monkey: String, value: Rational },
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveError::UnknownMonkey { name } => write!(f, "no monkey is called {}", name),
+            SolveError::Cycle { monkey } => write!(f, "{} depends on itself", monkey),
+            SolveError::DivisionByZero { monkey } => write!(f, "{} divides by zero", monkey),
+            SolveError::NonLinear { monkey } => {
+                write!(f, "{} makes the equation non-linear in {}", monkey, ME)
+            }
+            SolveError::Overflow { monkey } => write!(f, "{} overflows 128 bits", monkey),
+            SolveError::NoSolution => write!(f, "no value of {} passes the test", ME),
+            SolveError::AnySolution => write!(f, "every value of {} passes the test", ME),
+            SolveError::NotIntegral { monkey, value } => {
+                write!(f, "{} = {} is not an integer", monkey, value)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+fn parse_monkey(line: &str) -> (String, Expr) {
+    let (name, job) = line.split_once(": ").expect("Expected a monkey name!");
+    let job = Expr::parse(job).unwrap_or_else(|e| panic!("Invalid job {:?}: {}", job, e));
+
+    (name.to_owned(), job)
+}
+
+type Monkeys = HashMap<String, Expr>;
+
+const START: &str = "root";
+const ME: &str = "humn";
+
+/// What `name` yells, with `variable` left as the unknown if given.
+fn evaluate(
+    monkeys: &Monkeys,
+    name: &str,
+    variable: Option<&str>,
+    values: &mut HashMap<String, Option<Linear>>,
+) -> Result<Linear, SolveError> {
+    match values.get(name) {
+        Some(Some(value)) => return Ok(*value),
+        Some(None) => {
+            return Err(SolveError::Cycle {
+                monkey: name.to_owned(),
+            })
+        }
+        None => {}
+    }
+    if variable == Some(name) {
+        return Ok(Linear {
+            slope: Rational::integer(1),
+            offset: Rational::ZERO,
+        });
+    }
+
+    values.insert(name.to_owned(), None);
+    let job = monkeys.get(name).ok_or_else(|| SolveError::UnknownMonkey {
+        name: name.to_owned(),
+    })?;
+    let value = expression(monkeys, job, name, variable, values)?;
+    values.insert(name.to_owned(), Some(value));
+
+    Ok(value)
+}
+
+/// The value of `expr` in the job of `monkey`.
+fn expression(
+    monkeys: &Monkeys,
+    expr: &Expr,
+    monkey: &str,
+    variable: Option<&str>,
+    values: &mut HashMap<String, Option<Linear>>,
+) -> Result<Linear, SolveError> {
+    match expr {
+        Expr::Number(value) => Ok(Linear::constant(Rational::integer(*value))),
+        Expr::Variable(name) => evaluate(monkeys, name, variable, values),
+        Expr::Binary(operation, left, right) => {
+            let left = expression(monkeys, left, monkey, variable, values)?;
+            let right = expression(monkeys, right, monkey, variable, values)?;
+            left.apply(*operation, right, monkey)
+        }
+    }
+}
+
+fn integer(monkey: &str, value: Rational) -> Result<i128, SolveError> {
+    value.to_integer().ok_or_else(|| SolveError::NotIntegral {
+        monkey: monkey.to_owned(),
+        value,
+    })
+}
+
+fn yell(monkeys: &Monkeys) -> Result<i128, SolveError> {
+    let value = evaluate(monkeys, START, None, &mut HashMap::new())?.offset;
+    integer(START, value)
+}
+
+/// The value of `humn` for which both sides of `root` are equal.
+fn solve(monkeys: &Monkeys) -> Result<i128, SolveError> {
+    let Some(Expr::Binary(_, left, right)) = monkeys.get(START) else {
+        return Err(SolveError::UnknownMonkey {
+            name: START.to_owned(),
+        });
+    };
+
+    let mut values = HashMap::new();
+    let left = expression(monkeys, left, START, Some(ME), &mut values)?;
+    let right = expression(monkeys, right, START, Some(ME), &mut values)?;
+
+    // left.slope * humn + left.offset = right.slope * humn + right.offset
+    let overflow = || SolveError::Overflow {
+        monkey: START.to_owned(),
+    };
+    let slope = left.slope.checked_sub(right.slope).ok_or_else(overflow)?;
+    let offset = right.offset.checked_sub(left.offset).ok_or_else(overflow)?;
+    if slope.is_zero() {
+        return Err(match offset.is_zero() {
+            true => SolveError::AnySolution,
+            false => SolveError::NoSolution,
+        });
+    }
+
+    let solution = offset.checked_div(slope).ok_or_else(overflow)?;
+    crate::debug!("{} = {}", ME, solution);
+    integer(ME, solution)
+}
+
+pub fn part_one(input: &str) -> Option<i128> {
+    let monkeys = input.lines().map(parse_monkey).collect::<Monkeys>();
+
+    yell(&monkeys)
+        .map_err(|error| crate::error!("{}", error))
+        .ok()
+}
+
+pub fn part_two(input: &str) -> Option<i128> {
+    let monkeys = input.lines().map(parse_monkey).collect::<Monkeys>();
+
+    solve(&monkeys)
+        .map_err(|error| crate::error!("{}", error))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = crate::read_file("examples", 21);
+        assert_eq!(part_one(&input), Some(152));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = crate::read_file("examples", 21);
+        assert_eq!(part_two(&input), Some(301));
+    }
+
+    fn solve_str(input: &str) -> Result<i128, SolveError> {
+        solve(&input.lines().map(parse_monkey).collect())
+    }
+
+    #[test]
+    fn test_solve() {
+        // humn appears on both sides and goes negative on the way.
+        let both = "root: aaaa + bbbb\naaaa: humn - cccc\ncccc: 10\nbbbb: dddd / eeee\n\
+                    dddd: humn * ffff\nffff: 3\neeee: 2\nhumn: 5";
+        assert_eq!(solve_str(both), Ok(-20));
+        let negative = "root: aaaa + bbbb\naaaa: humn + cccc\ncccc: 10\nbbbb: 4\nhumn: 5";
+        assert_eq!(solve_str(negative), Ok(-6));
+        let formula = "root: (aaaa - 1) * 3 + 6 / humn\naaaa: humn - 2\nhumn: 5";
+        assert_eq!(
+            solve_str(formula),
+            Err(SolveError::NonLinear {
+                monkey: "root".to_owned()
+            })
+        );
+        let formula = "root: aaaa + (humn - 1) / 2\naaaa: 7\nhumn: 5";
+        assert_eq!(solve_str(formula), Ok(15));
+
+        let square = "root: aaaa + bbbb\naaaa: humn * humn\nbbbb: 4\nhumn: 5";
+        assert_eq!(
+            solve_str(square),
+            Err(SolveError::NonLinear {
+                monkey: "aaaa".to_owned()
+            })
+        );
+        let fraction = "root: aaaa + bbbb\naaaa: humn * cccc\ncccc: 3\nbbbb: 4\nhumn: 5";
+        assert_eq!(
+            solve_str(fraction),
+            Err(SolveError::NotIntegral {
+                monkey: ME.to_owned(),
+                value: Rational::new(4, 3).unwrap()
+            })
+        );
+        let cancelled = "root: aaaa + bbbb\naaaa: humn - humn\nbbbb: 4\nhumn: 5";
+        assert_eq!(solve_str(cancelled), Err(SolveError::NoSolution));
+
+        let huge = "root: aaaa + humn\naaaa: cccc * cccc\ncccc: dddd * dddd\n\
+                    dddd: 10000000000\nhumn: 5";
+        assert_eq!(
+            solve_str(huge),
+            Err(SolveError::Overflow {
+                monkey: "aaaa".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn test_yell() {
+        let yell_str = |input: &str| yell(&input.lines().map(parse_monkey).collect());
+
+        assert_eq!(yell_str("root: aaaa / 4\naaaa: 6 * 2"), Ok(3));
+        assert_eq!(
+            yell_str("root: aaaa / 4\naaaa: 6"),
+            Err(SolveError::NotIntegral {
+                monkey: START.to_owned(),
+                value: Rational::new(3, 2).unwrap()
+            })
+        );
+        // Fractions on the way are exact, only the answer has to be whole.
+        assert_eq!(yell_str("root: aaaa * 4\naaaa: 6 / 8"), Ok(3));
+        assert_eq!(
+            yell_str("root: aaaa * aaaa\naaaa: 170141183460469231731687303715884105727"),
+            Err(SolveError::Overflow {
+                monkey: START.to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn test_rational() {
+        let big = Rational::integer(i128::MAX);
+        assert_eq!(Rational::new(6, -4), Some(Rational::new(-3, 2).unwrap()));
+        assert_eq!(Rational::new(1, i128::MIN), None);
+        assert_eq!(big.checked_add(Rational::ONE), None);
+        // Cancelling before multiplying keeps results that fit.
+        let half = Rational::new(1, 2).unwrap();
+        assert_eq!(
+            Rational::new(i128::MAX - 1, 3)
+                .unwrap()
+                .checked_mul(Rational::new(3, i128::MAX - 1).unwrap()),
+            Some(Rational::ONE)
+        );
+        assert_eq!(
+            big.checked_mul(half)
+                .unwrap()
+                .checked_mul(Rational::integer(2)),
+            Some(big)
+        );
+    }
+}
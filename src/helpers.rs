@@ -2,3 +2,9 @@
  * Use this file if you want to extract helpers from your solutions.
  * Example import from this file: `use advent_of_code::helpers::example_fn;`.
  */
+pub mod expr;
+pub mod ocr;
+pub mod parallel;
+pub mod vm;
+
+pub use parallel::par_map;
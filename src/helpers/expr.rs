@@ -0,0 +1,426 @@
+//! Arithmetic expressions over integers and named variables, e.g. `(old + 3) * old` on day 11.
+//!
+//! Supports `+ - * /` with the usual precedence, left associativity and parentheses.
+//! Division truncates like Rust's `/` on integers.
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl BinOp {
+    fn from_char(c: char) -> Option<BinOp> {
+        match c {
+            '+' => Some(BinOp::Add),
+            '-' => Some(BinOp::Sub),
+            '*' => Some(BinOp::Mul),
+            '/' => Some(BinOp::Div),
+            _ => None,
+        }
+    }
+
+    pub fn precedence(&self) -> u8 {
+        match self {
+            BinOp::Add | BinOp::Sub => 1,
+            BinOp::Mul | BinOp::Div => 2,
+        }
+    }
+
+    /// The result, or `None` on overflow or division by zero.
+    pub fn apply(&self, left: i128, right: i128) -> Option<i128> {
+        match self {
+            BinOp::Add => left.checked_add(right),
+            BinOp::Sub => left.checked_sub(right),
+            BinOp::Mul => left.checked_mul(right),
+            BinOp::Div => left.checked_div(right),
+        }
+    }
+}
+
+impl Display for BinOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BinOp::Add => write!(f, "+"),
+            BinOp::Sub => write!(f, "-"),
+            BinOp::Mul => write!(f, "*"),
+            BinOp::Div => write!(f, "/"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Token {
+    Number(i128),
+    Name(String),
+    Op(BinOp),
+    Open,
+    Close,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    UnexpectedEnd,
+    Unexpected { position: usize, found: String },
+    Unclosed { position: usize },
+    InvalidNumber { position: usize },
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::UnexpectedEnd => write!(f, "unexpected end of expression"),
+            ParseError::Unexpected { position, found } => {
+                write!(f, "unexpected {} at {}", found, position)
+            }
+            ParseError::Unclosed { position } => {
+                write!(f, "parenthesis opened at {} is never closed", position)
+            }
+            ParseError::InvalidNumber { position } => write!(f, "invalid number at {}", position),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Splits `text` into tokens along with the byte offset each starts at.
+pub fn tokenize(text: &str) -> Result<Vec<(usize, Token)>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((position, c)) = chars.next() {
+        let mut take_while = |start: usize, f: fn(&char) -> bool| {
+            let mut end = start + c.len_utf8();
+            while let Some((i, c)) = chars.next_if(|(_, c)| f(c)) {
+                end = i + c.len_utf8();
+            }
+            &text[start..end]
+        };
+
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::Open,
+            ')' => Token::Close,
+            c if c.is_ascii_digit() => {
+                let digits = take_while(position, char::is_ascii_digit);
+                let number = digits
+                    .parse()
+                    .map_err(|_| ParseError::InvalidNumber { position })?;
+                Token::Number(number)
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let name = take_while(position, |c| c.is_alphanumeric() || *c == '_');
+                Token::Name(name.to_owned())
+            }
+            c => Token::Op(BinOp::from_char(c).ok_or(ParseError::Unexpected {
+                position,
+                found: format!("{:?}", c),
+            })?),
+        };
+        tokens.push((position, token));
+    }
+
+    Ok(tokens)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    Number(i128),
+    Variable(String),
+    Binary(BinOp, Box<Expr>, Box<Expr>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EvalError {
+    Unbound(String),
+    /// Overflow or division by zero.
+    Undefined {
+        op: BinOp,
+        left: i128,
+        right: i128,
+    },
+}
+
+impl Display for EvalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EvalError::Unbound(name) => write!(f, "{} is not bound", name),
+            EvalError::Undefined { op, left, right } => {
+                write!(f, "{} {} {} is out of range", left, op, right)
+            }
+        }
+    }
+}
+
+impl std::error::Error for EvalError {}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    next: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.next).map(|(_, token)| token)
+    }
+
+    fn unexpected(&self) -> ParseError {
+        match self.tokens.get(self.next) {
+            Some((position, token)) => ParseError::Unexpected {
+                position: *position,
+                found: format!("{:?}", token),
+            },
+            None => ParseError::UnexpectedEnd,
+        }
+    }
+
+    /// Parses operators binding at least as tightly as `min`, by precedence climbing.
+    fn expression(&mut self, min: u8) -> Result<Expr, ParseError> {
+        let mut left = self.atom()?;
+        while let Some(&Token::Op(op)) = self.peek() {
+            if op.precedence() < min {
+                break;
+            }
+            self.next += 1;
+            let right = self.expression(op.precedence() + 1)?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn atom(&mut self) -> Result<Expr, ParseError> {
+        let Some((position, token)) = self.tokens.get(self.next).cloned() else {
+            return Err(ParseError::UnexpectedEnd);
+        };
+
+        let expr = match token {
+            Token::Number(n) => Expr::Number(n),
+            Token::Name(name) => Expr::Variable(name),
+            Token::Open => {
+                self.next += 1;
+                let inner = self.expression(0)?;
+                if self.peek() != Some(&Token::Close) {
+                    return Err(match self.peek() {
+                        None => ParseError::Unclosed { position },
+                        Some(_) => self.unexpected(),
+                    });
+                }
+                inner
+            }
+            Token::Op(_) | Token::Close => return Err(self.unexpected()),
+        };
+        self.next += 1;
+
+        Ok(expr)
+    }
+}
+
+impl Expr {
+    pub fn parse(text: &str) -> Result<Expr, ParseError> {
+        let mut parser = Parser {
+            tokens: tokenize(text)?,
+            next: 0,
+        };
+        let expr = parser.expression(0)?;
+        if parser.peek().is_some() {
+            return Err(parser.unexpected());
+        }
+
+        Ok(expr)
+    }
+
+    /// Evaluates the expression with the variables given in `bindings`.
+    pub fn eval(&self, bindings: &[(&str, i128)]) -> Result<i128, EvalError> {
+        match self {
+            Expr::Number(n) => Ok(*n),
+            Expr::Variable(name) => bindings
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|&(_, value)| value)
+                .ok_or_else(|| EvalError::Unbound(name.clone())),
+            Expr::Binary(op, left, right) => {
+                let (left, right) = (left.eval(bindings)?, right.eval(bindings)?);
+                op.apply(left, right).ok_or(EvalError::Undefined {
+                    op: *op,
+                    left,
+                    right,
+                })
+            }
+        }
+    }
+
+    /// Replaces every operation on two constants with its result, leaving those that
+    /// overflow or divide by zero in place.
+    pub fn fold(&self) -> Expr {
+        match self {
+            Expr::Binary(op, left, right) => match (left.fold(), right.fold()) {
+                (Expr::Number(l), Expr::Number(r)) if op.apply(l, r).is_some() => {
+                    Expr::Number(op.apply(l, r).unwrap())
+                }
+                (left, right) => Expr::Binary(*op, Box::new(left), Box::new(right)),
+            },
+            other => other.clone(),
+        }
+    }
+
+    /// Whether `op` appears anywhere in the expression.
+    pub fn uses(&self, op: BinOp) -> bool {
+        match self {
+            Expr::Binary(o, left, right) => *o == op || left.uses(op) || right.uses(op),
+            _ => false,
+        }
+    }
+
+    /// The names of the variables, in order of their first appearance.
+    pub fn variables(&self) -> Vec<&str> {
+        let mut names = Vec::new();
+        self.collect_variables(&mut names);
+        names
+    }
+
+    fn collect_variables<'a>(&'a self, names: &mut Vec<&'a str>) {
+        match self {
+            Expr::Number(_) => {}
+            Expr::Variable(name) => {
+                if !names.contains(&name.as_str()) {
+                    names.push(name);
+                }
+            }
+            Expr::Binary(_, left, right) => {
+                left.collect_variables(names);
+                right.collect_variables(names);
+            }
+        }
+    }
+
+    fn write(&self, f: &mut std::fmt::Formatter<'_>, min: u8) -> std::fmt::Result {
+        match self {
+            // there is no unary minus, so negative numbers, e.g. from folding, are subtractions.
+            Expr::Number(n) if *n < 0 => write!(f, "(0 - {})", n.unsigned_abs()),
+            Expr::Number(n) => write!(f, "{}", n),
+            Expr::Variable(name) => write!(f, "{}", name),
+            Expr::Binary(op, left, right) => {
+                let parenthesize = op.precedence() < min;
+                if parenthesize {
+                    write!(f, "(")?;
+                }
+                left.write(f, op.precedence())?;
+                write!(f, " {} ", op)?;
+                right.write(f, op.precedence() + 1)?;
+                if parenthesize {
+                    write!(f, ")")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Writes the expression with only the parentheses it needs to parse back the same,
+/// except for negative numbers, which parse back as `0 - n`.
+impl Display for Expr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.write(f, 0)
+    }
+}
+
+impl FromStr for Expr {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Expr::parse(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            tokenize("(old+12)*x_1").unwrap(),
+            [
+                (0, Token::Open),
+                (1, Token::Name("old".to_owned())),
+                (4, Token::Op(BinOp::Add)),
+                (5, Token::Number(12)),
+                (7, Token::Close),
+                (8, Token::Op(BinOp::Mul)),
+                (9, Token::Name("x_1".to_owned())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_precedence() {
+        let expr: Expr = "10 - 2 - 3 * (4 + old) / 2".parse().unwrap();
+        assert_eq!(expr.to_string(), "10 - 2 - 3 * (4 + old) / 2");
+        assert_eq!(expr.eval(&[("old", 2)]), Ok(10 - 2 - 3 * 6 / 2));
+        assert_eq!(
+            "a - (b - c)"
+                .parse::<Expr>()
+                .unwrap()
+                .eval(&[("a", 1), ("b", 2), ("c", 3)]),
+            Ok(2)
+        );
+        assert_eq!("((a))".parse::<Expr>().unwrap().to_string(), "a");
+        assert_eq!(
+            "a - (b + c)".parse::<Expr>().unwrap().to_string(),
+            "a - (b + c)"
+        );
+    }
+
+    #[test]
+    fn test_fold() {
+        let expr: Expr = "2 * 3 + x * (8 / 4) + 1 / 0".parse().unwrap();
+        assert_eq!(expr.fold().to_string(), "6 + x * 2 + 1 / 0");
+        assert_eq!(expr.variables(), ["x"]);
+        assert!(expr.uses(BinOp::Div));
+        assert_eq!(
+            expr.eval(&[("x", 1)]),
+            Err(EvalError::Undefined {
+                op: BinOp::Div,
+                left: 1,
+                right: 0
+            })
+        );
+        assert_eq!(expr.eval(&[]), Err(EvalError::Unbound("x".to_owned())));
+
+        let folded = "x * (1 - 2) - 3".parse::<Expr>().unwrap().fold();
+        assert_eq!(folded.to_string(), "x * (0 - 1) - 3");
+        let parsed: Expr = folded.to_string().parse().unwrap();
+        assert_eq!(parsed.fold(), folded);
+        assert_eq!(parsed.eval(&[("x", 5)]), Ok(-8));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        use ParseError::*;
+
+        assert_eq!(Expr::parse("1 +"), Err(UnexpectedEnd));
+        assert_eq!(Expr::parse(" (1 + 2"), Err(Unclosed { position: 1 }));
+        assert_eq!(
+            Expr::parse("1 % 2"),
+            Err(Unexpected {
+                position: 2,
+                found: "'%'".to_owned()
+            })
+        );
+        assert_eq!(
+            Expr::parse("a b"),
+            Err(Unexpected {
+                position: 2,
+                found: "Name(\"b\")".to_owned()
+            })
+        );
+        assert_eq!(
+            Expr::parse("1 + 99999999999999999999999999999999999999999"),
+            Err(InvalidNumber { position: 4 })
+        );
+    }
+}
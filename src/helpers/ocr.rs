@@ -0,0 +1,268 @@
+//! Reads the block letters that some puzzles draw on a screen of `#` and `.` pixels.
+//!
+//! Both the 4x6 font (e.g. 2022 day 10) and the 6x10 font (e.g. 2018 day 10) are supported.
+//! Letters are separated by empty columns, so the screen does not need a fixed cell width.
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::env;
+use std::fmt::Display;
+
+/// When set, [`Screen`] displays its pixels in addition to the decoded letters.
+pub const SHOW_ART_ENV: &str = "AOC_SHOW_ART";
+
+const FONT_6: &[(char, &str)] = &[
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', ".###\n..#.\n..#.\n..#.\n..#.\n.###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+const FONT_10: &[(char, &str)] = &[
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+lazy_static! {
+    static ref FONTS: HashMap<usize, HashMap<String, char>> = [(6, FONT_6), (10, FONT_10)]
+        .into_iter()
+        .map(|(height, font)| {
+            let glyphs = font
+                .iter()
+                .flat_map(|(letter, art)| {
+                    glyphs(&pixels(art))
+                        .into_iter()
+                        .map(move |glyph| (glyph, *letter))
+                })
+                .collect();
+            (height, glyphs)
+        })
+        .collect();
+}
+
+fn is_lit(pixel: char) -> bool {
+    pixel == '#' || pixel == '█'
+}
+
+fn pixels(art: &str) -> Vec<Vec<bool>> {
+    art.lines()
+        .map(|line| line.trim_end())
+        .filter(|line| !line.is_empty())
+        .map(|line| line.chars().map(is_lit).collect())
+        .collect()
+}
+
+/// Splits the screen at empty columns and renders every letter as `#`/`.` rows.
+fn glyphs(pixels: &[Vec<bool>]) -> Vec<String> {
+    let width = pixels.iter().map(|row| row.len()).max().unwrap_or(0);
+    let lit = |x: usize, y: usize| pixels[y].get(x).copied().unwrap_or(false);
+    let is_empty = |x: usize| (0..pixels.len()).all(|y| !lit(x, y));
+
+    let mut glyphs = Vec::new();
+    let mut x = 0;
+    while x < width {
+        if is_empty(x) {
+            x += 1;
+            continue;
+        }
+
+        let start = x;
+        while x < width && !is_empty(x) {
+            x += 1;
+        }
+
+        let glyph = (0..pixels.len())
+            .map(|y| {
+                (start..x)
+                    .map(|x| if lit(x, y) { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        glyphs.push(glyph);
+    }
+
+    glyphs
+}
+
+/// Decodes the letters drawn on `art`, picking the font by the number of rows.
+/// Returns `None` if the screen contains anything that is not a known letter.
+pub fn decode(art: &str) -> Option<String> {
+    let pixels = pixels(art);
+    let font = FONTS.get(&pixels.len())?;
+
+    let glyphs = glyphs(&pixels);
+    if glyphs.is_empty() {
+        return None;
+    }
+
+    glyphs
+        .iter()
+        .map(|glyph| font.get(glyph).copied())
+        .collect()
+}
+
+/// Pixels drawn by a puzzle. Displays as the decoded letters, or as the pixels themselves
+/// if they cannot be decoded. `{:#}` or setting `AOC_SHOW_ART` displays both.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Screen {
+    art: String,
+}
+
+impl Screen {
+    pub fn new(art: String) -> Screen {
+        Screen { art }
+    }
+
+    pub fn art(&self) -> &str {
+        &self.art
+    }
+
+    pub fn letters(&self) -> Option<String> {
+        decode(&self.art)
+    }
+}
+
+impl Display for Screen {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let show_art = f.alternate() || env::var_os(SHOW_ART_ENV).is_some_and(|v| !v.is_empty());
+
+        match self.letters() {
+            Some(letters) if show_art => write!(f, "{}\n{}", self.art.trim_end(), letters),
+            Some(letters) => write!(f, "{}", letters),
+            None => write!(f, "{}", self.art.trim_end()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(font: &[(char, &str)], text: &str) -> String {
+        let letters = text
+            .chars()
+            .map(|c| font.iter().find(|(letter, _)| *letter == c).unwrap().1)
+            .map(|art| art.lines().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        (0..letters[0].len())
+            .map(|y| {
+                letters
+                    .iter()
+                    .map(|letter| format!("{}.", letter[y]))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_decode_small_font() {
+        let art = "\
+####.#..#.####.####.####.#..#..##..####.
+#....#..#....#.#.......#.#..#.#..#....#.
+###..####...#..###....#..####.#......#..
+#....#..#..#...#.....#...#..#.#.....#...
+#....#..#.#....#....#....#..#.#..#.#....
+####.#..#.####.#....####.#..#..##..####.";
+
+        assert_eq!(decode(art), Some("EHZFZHCZ".to_string()));
+        assert_eq!(
+            decode(&render(FONT_6, "ABCEFGHIJKLOPRSUYZ")),
+            Some("ABCEFGHIJKLOPRSUYZ".to_string())
+        );
+    }
+
+    #[test]
+    fn test_decode_large_font() {
+        assert_eq!(
+            decode(&render(FONT_10, "ABCEFGHJKLNPRXZ")),
+            Some("ABCEFGHJKLNPRXZ".to_string())
+        );
+    }
+
+    #[test]
+    fn test_decode_unknown() {
+        assert_eq!(decode("#.#\n.#.\n#.#"), None);
+        assert_eq!(decode(&"##..##..\n".repeat(6)), None);
+    }
+
+    #[test]
+    fn test_screen_display() {
+        let screen = Screen::new(render(FONT_6, "HI"));
+        assert_eq!(screen.to_string(), "HI");
+        assert_eq!(format!("{:#}", screen), format!("{}\nHI", screen.art()));
+    }
+}
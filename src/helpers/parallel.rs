@@ -0,0 +1,153 @@
+//! Runs independent work on a bounded number of threads, e.g. one search per blueprint.
+use std::panic;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Mutex};
+use std::thread;
+
+use crate::params;
+
+/// A fixed number of workers that take items off a shared queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pool {
+    workers: usize,
+}
+
+impl Pool {
+    pub fn new(workers: usize) -> Pool {
+        Pool {
+            workers: workers.max(1),
+        }
+    }
+
+    pub fn workers(&self) -> usize {
+        self.workers
+    }
+
+    /// Applies `func` to every item and returns the results in the order of the items.
+    ///
+    /// If `func` panics, the remaining items are skipped and the panic is resumed on the
+    /// calling thread once all workers have stopped. Workers use the same [`params`] as the
+    /// calling thread.
+    pub fn map<T, R, F>(&self, items: impl IntoIterator<Item = T>, func: F) -> Vec<R>
+    where
+        T: Send,
+        R: Send,
+        F: Fn(T) -> R + Sync,
+    {
+        let items: Vec<T> = items.into_iter().collect();
+        let len = items.len();
+        if len == 0 {
+            return Vec::new();
+        }
+
+        let queue = Mutex::new(items.into_iter().enumerate());
+        let stop = AtomicBool::new(false);
+        let (sender, results) = mpsc::channel();
+        let source = params::source();
+
+        let panicked = thread::scope(|scope| {
+            let workers = (0..self.workers.min(len))
+                .map(|_| {
+                    let sender = sender.clone();
+                    let (queue, stop, func) = (&queue, &stop, &func);
+                    scope.spawn(move || {
+                        params::inherit(source);
+                        while !stop.load(Ordering::Relaxed) {
+                            let next = queue.lock().unwrap_or_else(|e| e.into_inner()).next();
+                            let Some((index, item)) = next else {
+                                break;
+                            };
+
+                            match panic::catch_unwind(panic::AssertUnwindSafe(|| func(item))) {
+                                Ok(result) => sender.send((index, result)).unwrap(),
+                                Err(payload) => {
+                                    stop.store(true, Ordering::Relaxed);
+                                    return Some(payload);
+                                }
+                            }
+                        }
+                        None
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            workers
+                .into_iter()
+                .filter_map(|worker| worker.join().unwrap())
+                .next()
+        });
+
+        if let Some(payload) = panicked {
+            panic::resume_unwind(payload);
+        }
+
+        drop(sender);
+        let mut results = results.into_iter().collect::<Vec<_>>();
+        results.sort_unstable_by_key(|(index, _)| *index);
+        results.into_iter().map(|(_, result)| result).collect()
+    }
+}
+
+impl Default for Pool {
+    /// One worker per available core.
+    fn default() -> Pool {
+        Pool::new(thread::available_parallelism().map_or(1, |n| n.get()))
+    }
+}
+
+/// [`Pool::map`] on a pool with one worker per available core.
+pub fn par_map<T, R, F>(items: impl IntoIterator<Item = T>, func: F) -> Vec<R>
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> R + Sync,
+{
+    Pool::default().map(items, func)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    #[test]
+    fn test_keeps_order() {
+        let result = Pool::new(4).map(0..100_u64, |n| {
+            // finish out of order.
+            thread::sleep(std::time::Duration::from_micros((100 - n) * 10));
+            n * n
+        });
+        assert_eq!(result, (0..100).map(|n| n * n).collect::<Vec<_>>());
+        assert_eq!(par_map(Vec::<u32>::new(), |n| n), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn test_bounded_workers() {
+        let running = AtomicUsize::new(0);
+        let max = AtomicUsize::new(0);
+
+        Pool::new(3).map(0..30, |_| {
+            let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+            max.fetch_max(now, Ordering::SeqCst);
+            thread::sleep(std::time::Duration::from_millis(1));
+            running.fetch_sub(1, Ordering::SeqCst);
+        });
+
+        assert!(max.load(Ordering::SeqCst) <= 3);
+    }
+
+    #[test]
+    fn test_propagates_panic() {
+        let result = panic::catch_unwind(|| {
+            Pool::new(2).map(0..10, |n| {
+                if n == 7 {
+                    panic!("seven");
+                }
+                n
+            })
+        });
+
+        let payload = result.unwrap_err();
+        assert_eq!(payload.downcast_ref::<&str>(), Some(&"seven"));
+    }
+}
@@ -0,0 +1,375 @@
+//! A small virtual machine for assembly-style puzzles, e.g. the CPU driving the CRT on day 10.
+use std::collections::HashSet;
+use std::fmt::{Debug, Display, Write};
+use std::ops::{Index, IndexMut};
+
+/// An instruction set a [`Vm`] can run.
+pub trait Instruction: Debug + Sized {
+    /// The registers of the machine and their initial values.
+    const REGISTERS: &'static [(&'static str, i64)];
+
+    /// Decodes one line of a program.
+    fn decode(line: &str) -> Result<Self, String>;
+
+    /// How many cycles the instruction takes to complete.
+    fn cycles(&self) -> u32;
+
+    /// Applies the instruction at the end of its last cycle.
+    fn execute(&self, registers: &mut Registers) -> Flow;
+}
+
+/// Where to continue after an instruction completes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flow {
+    Next,
+    /// Jumps relative to the instruction that just completed.
+    Jump(i64),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Registers {
+    names: &'static [(&'static str, i64)],
+    values: Vec<i64>,
+}
+
+impl Registers {
+    fn new(names: &'static [(&'static str, i64)]) -> Registers {
+        Registers {
+            names,
+            values: names.iter().map(|&(_, value)| value).collect(),
+        }
+    }
+
+    fn position(&self, name: &str) -> usize {
+        self.names
+            .iter()
+            .position(|&(n, _)| n == name)
+            .unwrap_or_else(|| panic!("Unknown register {}", name))
+    }
+}
+
+impl Index<&str> for Registers {
+    type Output = i64;
+
+    fn index(&self, name: &str) -> &i64 {
+        &self.values[self.position(name)]
+    }
+}
+
+impl IndexMut<&str> for Registers {
+    fn index_mut(&mut self, name: &str) -> &mut i64 {
+        let position = self.position(name);
+        &mut self.values[position]
+    }
+}
+
+impl Display for Registers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, ((name, _), value)) in self.names.iter().zip(&self.values).enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}={}", name, value)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Breakpoint {
+    /// Stops once this many cycles have completed.
+    Cycle(u64),
+    /// Stops before the instruction at this address starts.
+    Address(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    Halted,
+    Breakpoint(Breakpoint),
+}
+
+/// An instruction that completed, as recorded in the trace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub cycle: u64,
+    pub address: usize,
+    pub instruction: String,
+    pub registers: Registers,
+}
+
+type Hook<'a> = Box<dyn FnMut(u64, &Registers) + 'a>;
+
+pub struct Vm<'a, I: Instruction> {
+    program: Vec<I>,
+    address: usize,
+    cycle: u64,
+    /// Cycles left on the instruction at `address` once it has started.
+    remaining: Option<u32>,
+    registers: Registers,
+    hooks: Vec<Hook<'a>>,
+    breakpoints: HashSet<Breakpoint>,
+    resuming: bool,
+    trace: Option<Vec<Step>>,
+}
+
+impl<'a, I: Instruction> Vm<'a, I> {
+    pub fn new(program: Vec<I>) -> Vm<'a, I> {
+        Vm {
+            program,
+            address: 0,
+            cycle: 0,
+            remaining: None,
+            registers: Registers::new(I::REGISTERS),
+            hooks: Vec::new(),
+            breakpoints: HashSet::new(),
+            resuming: false,
+            trace: None,
+        }
+    }
+
+    /// Decodes every non-empty line of `source`.
+    pub fn load(source: &str) -> Result<Vm<'a, I>, String> {
+        let program = source
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| I::decode(line).map_err(|e| format!("line {}: {}", i + 1, e)))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Vm::new(program))
+    }
+
+    pub fn cycle(&self) -> u64 {
+        self.cycle
+    }
+
+    pub fn address(&self) -> usize {
+        self.address
+    }
+
+    pub fn registers(&self) -> &Registers {
+        &self.registers
+    }
+
+    pub fn registers_mut(&mut self) -> &mut Registers {
+        &mut self.registers
+    }
+
+    /// Calls `hook` during every cycle with its number, counting from one, and the
+    /// registers before the instruction finishing in that cycle is applied.
+    pub fn on_cycle(&mut self, hook: impl FnMut(u64, &Registers) + 'a) {
+        self.hooks.push(Box::new(hook));
+    }
+
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) {
+        self.breakpoints.insert(breakpoint);
+    }
+
+    pub fn remove_breakpoint(&mut self, breakpoint: Breakpoint) {
+        self.breakpoints.remove(&breakpoint);
+    }
+
+    /// Records every completed instruction from now on.
+    pub fn enable_trace(&mut self) {
+        self.trace.get_or_insert_with(Vec::new);
+    }
+
+    pub fn trace(&self) -> &[Step] {
+        self.trace.as_deref().unwrap_or_default()
+    }
+
+    /// The trace with one completed instruction per line.
+    pub fn trace_dump(&self) -> String {
+        self.trace().iter().fold(String::new(), |mut dump, step| {
+            let instruction = format!("{:<4} {}", step.address, step.instruction);
+            let _ = writeln!(
+                dump,
+                "{:>6}  {:<24} {}",
+                step.cycle, instruction, step.registers
+            );
+            dump
+        })
+    }
+
+    pub fn halted(&self) -> bool {
+        self.remaining.is_none() && self.address >= self.program.len()
+    }
+
+    /// Runs until the program ends or a breakpoint is hit. Running again resumes
+    /// after the breakpoint.
+    pub fn run(&mut self) -> Stop {
+        loop {
+            if self.remaining.is_none() && !std::mem::take(&mut self.resuming) {
+                let address = Breakpoint::Address(self.address);
+                if self.breakpoints.contains(&address) {
+                    self.resuming = true;
+                    return Stop::Breakpoint(address);
+                }
+            }
+
+            if !self.step() {
+                return Stop::Halted;
+            }
+
+            let cycle = Breakpoint::Cycle(self.cycle);
+            if self.breakpoints.contains(&cycle) {
+                return Stop::Breakpoint(cycle);
+            }
+        }
+    }
+
+    /// Runs a single cycle, or returns `false` if the program has ended.
+    pub fn step(&mut self) -> bool {
+        if self.halted() {
+            return false;
+        }
+        self.resuming = false;
+
+        let instruction = &self.program[self.address];
+        let remaining = self.remaining.unwrap_or_else(|| instruction.cycles());
+
+        self.cycle += 1;
+        for hook in self.hooks.iter_mut() {
+            hook(self.cycle, &self.registers);
+        }
+
+        if remaining > 1 {
+            self.remaining = Some(remaining - 1);
+            return true;
+        }
+
+        let flow = instruction.execute(&mut self.registers);
+        if let Some(trace) = &mut self.trace {
+            trace.push(Step {
+                cycle: self.cycle,
+                address: self.address,
+                instruction: format!("{:?}", instruction),
+                registers: self.registers.clone(),
+            });
+        }
+
+        self.remaining = None;
+        self.address = match flow {
+            Flow::Next => self.address + 1,
+            Flow::Jump(offset) => match self.address.checked_add_signed(offset as isize) {
+                Some(address) => address,
+                None => self.program.len(),
+            },
+        };
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    enum Toy {
+        Inc(&'static str),
+        Dec(&'static str),
+        Jnz(&'static str, i64),
+    }
+
+    fn register(name: &str) -> Result<&'static str, String> {
+        match name {
+            "a" => Ok("a"),
+            "b" => Ok("b"),
+            _ => Err(format!("unknown register {}", name)),
+        }
+    }
+
+    impl Instruction for Toy {
+        const REGISTERS: &'static [(&'static str, i64)] = &[("a", 0), ("b", 3)];
+
+        fn decode(line: &str) -> Result<Self, String> {
+            let parts: Vec<_> = line.split(' ').collect();
+            match parts[..] {
+                ["inc", r] => Ok(Toy::Inc(register(r)?)),
+                ["dec", r] => Ok(Toy::Dec(register(r)?)),
+                ["jnz", r, offset] => Ok(Toy::Jnz(
+                    register(r)?,
+                    offset.parse().map_err(|_| "invalid offset")?,
+                )),
+                _ => Err(format!("unknown instruction {:?}", line)),
+            }
+        }
+
+        fn cycles(&self) -> u32 {
+            match self {
+                Toy::Jnz(_, _) => 2,
+                _ => 1,
+            }
+        }
+
+        fn execute(&self, registers: &mut Registers) -> Flow {
+            match *self {
+                Toy::Inc(r) => registers[r] += 1,
+                Toy::Dec(r) => registers[r] -= 1,
+                Toy::Jnz(r, offset) if registers[r] != 0 => return Flow::Jump(offset),
+                Toy::Jnz(_, _) => {}
+            }
+            Flow::Next
+        }
+    }
+
+    const PROGRAM: &str = "inc a\ninc a\ndec b\njnz b -3\n";
+
+    #[test]
+    fn test_run() {
+        let mut during = Vec::new();
+        let mut vm = Vm::<Toy>::load(PROGRAM).unwrap();
+        vm.on_cycle(|cycle, registers| during.push((cycle, registers["a"])));
+        assert_eq!(vm.run(), Stop::Halted);
+        assert_eq!(vm.registers()["a"], 6);
+        assert_eq!(vm.registers()["b"], 0);
+        assert_eq!(vm.cycle(), 15);
+        drop(vm);
+
+        assert_eq!(during.len(), 15);
+        assert_eq!(during[..3], [(1, 0), (2, 1), (3, 2)]);
+    }
+
+    #[test]
+    fn test_breakpoints() {
+        let mut vm = Vm::<Toy>::load(PROGRAM).unwrap();
+        vm.add_breakpoint(Breakpoint::Address(2));
+        vm.add_breakpoint(Breakpoint::Cycle(7));
+
+        assert_eq!(vm.run(), Stop::Breakpoint(Breakpoint::Address(2)));
+        assert_eq!(vm.cycle(), 2);
+        assert_eq!(vm.run(), Stop::Breakpoint(Breakpoint::Cycle(7)));
+        assert_eq!(vm.run(), Stop::Breakpoint(Breakpoint::Address(2)));
+        assert_eq!((vm.cycle(), vm.registers()["b"]), (7, 2));
+
+        vm.remove_breakpoint(Breakpoint::Address(2));
+        assert_eq!(vm.run(), Stop::Halted);
+    }
+
+    #[test]
+    fn test_trace() {
+        let mut vm = Vm::<Toy>::load(PROGRAM).unwrap();
+        vm.enable_trace();
+        vm.run();
+
+        assert_eq!(vm.trace().len(), 12);
+        let dump = vm.trace_dump();
+        assert_eq!(dump.lines().count(), 12);
+        assert_eq!(
+            dump.lines()
+                .nth(3)
+                .unwrap()
+                .split_whitespace()
+                .collect::<Vec<_>>(),
+            ["5", "3", "Jnz(\"b\",", "-3)", "a=2", "b=2"]
+        );
+    }
+
+    #[test]
+    fn test_decode_error() {
+        let error = Vm::<Toy>::load("inc a\ninc c\n").err().unwrap();
+        assert_eq!(error, "line 2: unknown register c");
+    }
+}
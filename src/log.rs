@@ -0,0 +1,157 @@
+//! Leveled logging to stderr, so debug output never mixes with the reported results.
+//!
+//! The verbosity is read from `AOC_LOG` (`off`, `error`, `warn`, `info`, `debug` or `trace`)
+//! and raised by passing `-v`, `-vv` or `-vvv`, e.g. `cargo solve 19 -- -vv`.
+//! Log with `advent_of_code::debug!("seen {} states", seen.len())` and friends.
+use lazy_static::lazy_static;
+use std::env;
+use std::fmt::{self, Display};
+
+pub const LOG_ENV: &str = "AOC_LOG";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    const ALL: [Level; 5] = [
+        Level::Error,
+        Level::Warn,
+        Level::Info,
+        Level::Debug,
+        Level::Trace,
+    ];
+
+    pub fn from_name(name: &str) -> Option<Option<Level>> {
+        match name.to_ascii_lowercase().as_str() {
+            "off" => Some(None),
+            name => Level::ALL
+                .into_iter()
+                .find(|level| level.to_string() == name)
+                .map(Some),
+        }
+    }
+
+    /// The level enabled by passing `-v` `verbosity` times, starting at [`Level::Warn`].
+    pub fn from_verbosity(verbosity: usize) -> Level {
+        Level::ALL[(Level::Warn as usize + verbosity).min(Level::ALL.len() - 1)]
+    }
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Counts `-v` flags, where `-vv` counts as two.
+pub fn verbosity(args: impl Iterator<Item = String>) -> usize {
+    args.filter(|arg| arg.len() > 1 && arg.starts_with('-') && arg[1..].chars().all(|c| c == 'v'))
+        .map(|arg| arg.len() - 1)
+        .sum()
+}
+
+fn max_level_from(env: Option<String>, verbosity: usize) -> Option<Level> {
+    let from_env = env
+        .and_then(|name| Level::from_name(&name))
+        .unwrap_or(Some(Level::Warn));
+
+    if verbosity > 0 {
+        from_env.max(Some(Level::from_verbosity(verbosity)))
+    } else {
+        from_env
+    }
+}
+
+lazy_static! {
+    static ref MAX_LEVEL: Option<Level> =
+        max_level_from(env::var(LOG_ENV).ok(), verbosity(env::args().skip(1)));
+}
+
+pub fn max_level() -> Option<Level> {
+    *MAX_LEVEL
+}
+
+pub fn enabled(level: Level) -> bool {
+    max_level().is_some_and(|max| level <= max)
+}
+
+pub fn log(level: Level, target: &str, args: fmt::Arguments) {
+    if enabled(level) {
+        eprintln!("[{:<5} {}] {}", level, target, args);
+    }
+}
+
+#[macro_export]
+macro_rules! error {
+    ($($arg:tt)+) => {
+        $crate::log::log($crate::log::Level::Error, module_path!(), format_args!($($arg)+))
+    };
+}
+
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)+) => {
+        $crate::log::log($crate::log::Level::Warn, module_path!(), format_args!($($arg)+))
+    };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)+) => {
+        $crate::log::log($crate::log::Level::Info, module_path!(), format_args!($($arg)+))
+    };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => {
+        $crate::log::log($crate::log::Level::Debug, module_path!(), format_args!($($arg)+))
+    };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => {
+        $crate::log::log($crate::log::Level::Trace, module_path!(), format_args!($($arg)+))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verbosity() {
+        let args = ["19", "-v", "--timeout", "-vv", "-x"].map(String::from);
+        assert_eq!(verbosity(args.into_iter()), 3);
+    }
+
+    #[test]
+    fn test_max_level() {
+        assert_eq!(max_level_from(None, 0), Some(Level::Warn));
+        assert_eq!(max_level_from(None, 2), Some(Level::Debug));
+        assert_eq!(max_level_from(None, 9), Some(Level::Trace));
+        assert_eq!(max_level_from(Some("off".to_string()), 0), None);
+        assert_eq!(
+            max_level_from(Some("TRACE".to_string()), 1),
+            Some(Level::Trace)
+        );
+        assert_eq!(
+            max_level_from(Some("nonsense".to_string()), 0),
+            Some(Level::Warn)
+        );
+    }
+}
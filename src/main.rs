@@ -2,45 +2,513 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
-use advent_of_code::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
-use std::process::Command;
+use advent_of_code::log::{Level, LOG_ENV};
+use advent_of_code::params::{self, PARAMS_ENV};
+use advent_of_code::reporter::{self, DayStatus, Event, Reporter, REPORTER_ENV};
+use std::env;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{self, Child, Command, ExitStatus, Stdio};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
 
-fn main() {
-    let total: f64 = (1..=25)
-        .map(|day| {
-            let day = format!("{:02}", day);
-
-            let cmd = Command::new("cargo")
-                .args(["run", "--release", "--bin", &day])
-                .output()
-                .unwrap();
-
-            println!("----------");
-            println!("{}| Day {} |{}", ANSI_BOLD, day, ANSI_RESET);
-            println!("----------");
-
-            let output = String::from_utf8(cmd.stdout).unwrap();
-            let is_empty = output.is_empty();
-
-            println!(
-                "{}",
-                if is_empty {
-                    "Not solved."
-                } else {
-                    output.trim()
+use itertools::Itertools;
+
+const USAGE: &str = "usage: `cargo all -- [--timeout <seconds>] [--memory <MiB>] [--cpu <seconds>] [--format pretty|plain|json|junit] [--param [<day>.]<name>=<value>]... [-v|-vv|-vvv]`";
+
+/// Limits applied to every solution process spawned by the runner.
+///
+/// `timeout` is a wall-clock limit that is applied to each part separately,
+/// `memory` and `cpu` are enforced for the whole process through `RLIMIT_AS` and `RLIMIT_CPU`.
+#[derive(Debug, Default)]
+struct Limits {
+    timeout: Option<Duration>,
+    memory: Option<u64>,
+    cpu: Option<u64>,
+}
+
+struct Options {
+    limits: Limits,
+    /// Log level passed on to the solutions, whose stderr is always shown.
+    log_level: Level,
+    /// Parameter overrides passed on to the solutions.
+    params: Vec<String>,
+}
+
+fn parse_args() -> Result<(Options, Box<dyn Reporter>), pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    let limits = Limits {
+        timeout: args
+            .opt_value_from_str::<_, f64>("--timeout")?
+            .map(Duration::from_secs_f64),
+        memory: args
+            .opt_value_from_str::<_, u64>("--memory")?
+            .map(|mib| mib * 1024 * 1024),
+        cpu: args.opt_value_from_str("--cpu")?,
+    };
+    let reporter = match args.opt_value_from_str::<_, String>("--format")? {
+        Some(name) => reporter::by_name(&name).ok_or(pico_args::Error::ArgumentParsingFailed {
+            cause: format!("unknown format: {}", name),
+        })?,
+        None => reporter::from_env(),
+    };
+
+    let params: Vec<String> = args.values_from_str("--param")?;
+    // checked here so that a typo is reported once instead of failing every day.
+    let inherited = env::var(PARAMS_ENV).unwrap_or_default();
+    let invalid = inherited
+        .split(',')
+        .filter(|text| !text.trim().is_empty())
+        .chain(params.iter().map(String::as_str))
+        .find(|text| !params::is_valid_override(text));
+    if let Some(invalid) = invalid {
+        return Err(pico_args::Error::ArgumentParsingFailed {
+            cause: format!(
+                "invalid parameter {:?}, expected [<day>.]<name>=<value>",
+                invalid
+            ),
+        });
+    }
+
+    let mut verbosity = 0;
+    for (flag, count) in [("-vvv", 3), ("-vv", 2), ("-v", 1)] {
+        while args.contains(flag) {
+            verbosity += count;
+        }
+    }
+    // errors and warnings are always shown, `-v` and `AOC_LOG` only add more.
+    let from_env = env::var(LOG_ENV)
+        .ok()
+        .and_then(|name| Level::from_name(&name));
+    let log_level = Level::from_verbosity(verbosity).max(from_env.flatten().unwrap_or(Level::Warn));
+
+    let remaining = args.finish();
+    if !remaining.is_empty() {
+        return Err(pico_args::Error::ArgumentParsingFailed {
+            cause: format!("unexpected arguments: {:?}", remaining),
+        });
+    }
+
+    Ok((
+        Options {
+            limits,
+            log_level,
+            params,
+        },
+        reporter,
+    ))
+}
+
+impl Limits {
+    #[cfg(unix)]
+    fn apply(&self, cmd: &mut Command) {
+        use std::os::unix::process::CommandExt;
+
+        let memory = self.memory;
+        let cpu = self.cpu;
+        if memory.is_none() && cpu.is_none() {
+            return;
+        }
+
+        let set_limit = |resource, soft: u64, hard: u64| {
+            let limit = libc::rlimit {
+                rlim_cur: soft as libc::rlim_t,
+                rlim_max: hard as libc::rlim_t,
+            };
+            // SAFETY: `setrlimit` only reads the struct we pass and is async-signal-safe.
+            if unsafe { libc::setrlimit(resource, &limit) } != 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(())
+        };
+
+        // SAFETY: the closure runs between `fork` and `exec` and only calls `setrlimit`.
+        unsafe {
+            cmd.pre_exec(move || {
+                if let Some(bytes) = memory {
+                    set_limit(libc::RLIMIT_AS, bytes, bytes)?;
+                }
+                if let Some(seconds) = cpu {
+                    // the soft limit raises SIGXCPU, the hard limit is a SIGKILL fallback.
+                    set_limit(libc::RLIMIT_CPU, seconds, seconds + 1)?;
                 }
-            );
+                Ok(())
+            });
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn apply(&self, _cmd: &mut Command) {
+        if self.memory.is_some() || self.cpu.is_some() {
+            eprintln!("--memory and --cpu are only supported on unix, ignoring.");
+        }
+    }
+}
+
+fn binary_path(day: &str) -> PathBuf {
+    let target_dir = env::var_os("CARGO_TARGET_DIR").unwrap_or_else(|| "target".into());
+    Path::new(&target_dir)
+        .join("release")
+        .join(format!("{}{}", day, env::consts::EXE_SUFFIX))
+}
+
+fn exists(day: &str) -> bool {
+    Path::new("src/bin").join(format!("{}.rs", day)).exists()
+}
+
+fn build(day: &str) -> bool {
+    // build separately so that compile times count neither towards the timeout nor the limits.
+    Command::new("cargo")
+        .args(["build", "--release", "--bin", day])
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+/// Waits for the next line of the child's output, giving up once `deadline` has passed.
+fn next_line(
+    lines: &mpsc::Receiver<String>,
+    deadline: Option<Instant>,
+) -> Result<String, RecvTimeoutError> {
+    match deadline {
+        Some(deadline) => lines.recv_timeout(deadline.saturating_duration_since(Instant::now())),
+        None => lines.recv().map_err(|_| RecvTimeoutError::Disconnected),
+    }
+}
+
+/// The CPU time used by all children that have been waited for so far.
+#[cfg(unix)]
+fn children_cpu_time() -> Duration {
+    // SAFETY: `getrusage` only writes to the struct we pass.
+    let usage = unsafe {
+        let mut usage = std::mem::zeroed::<libc::rusage>();
+        libc::getrusage(libc::RUSAGE_CHILDREN, &mut usage);
+        usage
+    };
+    let seconds = |time: libc::timeval| {
+        Duration::from_secs(time.tv_sec as u64) + Duration::from_micros(time.tv_usec as u64)
+    };
+    seconds(usage.ru_utime) + seconds(usage.ru_stime)
+}
+
+#[cfg(not(unix))]
+fn children_cpu_time() -> Duration {
+    Duration::ZERO
+}
+
+fn kill(child: &mut Child) {
+    // the process may have exited on its own in the meantime.
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+/// Runs a single day, forwarding everything it reports to `reporter`.
+/// Returns how the day ended and the time spent in the parts that finished.
+fn run(day: &str, options: &Options, reporter: &mut dyn Reporter) -> (DayStatus, Duration) {
+    let limits = &options.limits;
+    let mut elapsed = Duration::ZERO;
+    if !exists(day) {
+        return (DayStatus::NotSolved, elapsed);
+    }
+    if !build(day) {
+        let reason = "does not compile".to_string();
+        return (DayStatus::Failed { reason }, elapsed);
+    }
+
+    let mut cmd = Command::new(binary_path(day));
+    cmd.env(REPORTER_ENV, "json")
+        .env(LOG_ENV, options.log_level.to_string())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    if !options.params.is_empty() {
+        let inherited = env::var(PARAMS_ENV).ok();
+        let params = inherited.iter().chain(&options.params).join(",");
+        cmd.env(PARAMS_ENV, params);
+    }
+    limits.apply(&mut cmd);
+
+    let mut child = match cmd.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            let reason = e.to_string();
+            return (DayStatus::Failed { reason }, elapsed);
+        }
+    };
 
-            if is_empty {
-                0_f64
-            } else {
-                advent_of_code::parse_exec_time(&output)
+    let stdout = child.stdout.take().unwrap();
+    let (sender, lines) = mpsc::channel();
+    thread::spawn(move || {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            if sender.send(line).is_err() {
+                break;
             }
+        }
+    });
+
+    let stderr = child.stderr.take().unwrap();
+    let stderr = thread::spawn(move || {
+        let mut buffer = String::new();
+        for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+            eprintln!("{}", line);
+            buffer.push_str(&line);
+            buffer.push('\n');
+        }
+        buffer
+    });
+
+    let mut part = 0;
+    let mut deadline = limits.timeout.map(|timeout| Instant::now() + timeout);
+
+    loop {
+        match next_line(&lines, deadline) {
+            Ok(line) => {
+                match serde_json::from_str::<Event>(&line) {
+                    Ok(event) => {
+                        match &event {
+                            // every part restarts the clock.
+                            Event::PartStarted { part: started } => {
+                                part = *started;
+                                deadline = limits.timeout.map(|timeout| Instant::now() + timeout);
+                            }
+                            Event::BothStarted => {
+                                part = 1;
+                                deadline = limits.timeout.map(|timeout| Instant::now() + timeout);
+                            }
+                            Event::Parsed { elapsed: parsing } => elapsed += *parsing,
+                            Event::PartFinished(result) => elapsed += result.elapsed,
+                            Event::BothFinished(result) => elapsed += result.elapsed,
+                            _ => {}
+                        }
+                        reporter.event(&event);
+                    }
+                    Err(_) => reporter.output(&line),
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                kill(&mut child);
+                return (DayStatus::TimedOut { part }, elapsed);
+            }
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    // the build was waited for already, so only this child is added to the total.
+    let cpu_before = children_cpu_time();
+    let status = match child.wait() {
+        Ok(status) => status,
+        Err(e) => {
+            let reason = e.to_string();
+            return (DayStatus::Failed { reason }, elapsed);
+        }
+    };
+    let cpu_time = children_cpu_time().saturating_sub(cpu_before);
+    let stderr = stderr.join().unwrap_or_default();
+
+    (classify(status, &stderr, limits, part, cpu_time), elapsed)
+}
+
+/// The name of the signal that killed the process, if any.
+#[cfg(unix)]
+fn signal_name(status: ExitStatus) -> Option<String> {
+    use std::os::unix::process::ExitStatusExt;
+
+    let name = match status.signal()? {
+        libc::SIGABRT => "SIGABRT",
+        libc::SIGBUS => "SIGBUS",
+        libc::SIGFPE => "SIGFPE",
+        libc::SIGILL => "SIGILL",
+        libc::SIGKILL => "SIGKILL",
+        libc::SIGSEGV => "SIGSEGV",
+        libc::SIGTERM => "SIGTERM",
+        libc::SIGXCPU => "SIGXCPU",
+        signal => return Some(format!("signal {}", signal)),
+    };
+    Some(name.to_string())
+}
+
+#[cfg(not(unix))]
+fn signal_name(_status: ExitStatus) -> Option<String> {
+    None
+}
+
+/// Whether `stderr` shows that the process ran out of memory.
+fn out_of_memory(stderr: &str) -> bool {
+    // the default allocation error handler prints the first message before aborting, and the
+    // dynamic loader the second when it cannot even map the libraries.
+    let messages = ["memory allocation of", "failed to map segment"];
+    // threads, e.g. those of `par_map`, fail to spawn with EAGAIN once their stacks do not fit.
+    let spawn_failed = stderr.contains("failed to spawn thread") && stderr.contains("WouldBlock");
+
+    spawn_failed || messages.iter().any(|message| stderr.contains(message))
+}
+
+/// Tells hitting one of the `limits` apart from other failures, going only by what the limits
+/// leave behind: the signals of `RLIMIT_CPU` once `cpu_time` reached it, and the messages of
+/// failed allocations.
+fn classify(
+    status: ExitStatus,
+    stderr: &str,
+    limits: &Limits,
+    part: u8,
+    cpu_time: Duration,
+) -> DayStatus {
+    if status.success() {
+        return DayStatus::Finished;
+    }
+
+    #[cfg(unix)]
+    if let Some(seconds) = limits.cpu {
+        use std::os::unix::process::ExitStatusExt;
+
+        // the soft limit sends SIGXCPU, the hard limit one second later a SIGKILL. Any other
+        // SIGKILL, e.g. from the OOM killer, comes before the limit is used up.
+        let exhausted = cpu_time >= Duration::from_secs(seconds);
+        match status.signal() {
+            Some(libc::SIGXCPU) => return DayStatus::TimedOut { part },
+            Some(libc::SIGKILL) if exhausted => return DayStatus::TimedOut { part },
+            _ => {}
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = cpu_time;
+
+    if limits.memory.is_some() && out_of_memory(stderr) {
+        return DayStatus::OutOfMemory;
+    }
+
+    // panics print their location on one line and the message on the next.
+    let mut lines = stderr.lines().skip_while(|l| l.trim().is_empty());
+    let message = match (lines.next(), lines.next()) {
+        (Some(first), Some(message)) if first.ends_with(':') => {
+            Some(format!("{} {}", first, message))
+        }
+        (first, _) => first.map(str::to_string),
+    };
+    let reason = match (message, signal_name(status)) {
+        (Some(message), Some(signal)) => format!("{} (killed by {})", message, signal),
+        (None, Some(signal)) => format!("killed by {}", signal),
+        (Some(message), None) => message,
+        (None, None) => "unknown error".to_string(),
+    };
+
+    DayStatus::Failed { reason }
+}
+
+fn main() {
+    let (options, mut reporter) = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}", e);
+            eprintln!("{}", USAGE);
+            process::exit(1);
+        }
+    };
+
+    let total = (1..=25)
+        .map(|day| {
+            reporter.day_started(day);
+            let (status, elapsed) = run(&format!("{:02}", day), &options, reporter.as_mut());
+            reporter.day_finished(day, &status);
+            elapsed
         })
         .sum();
 
-    println!(
-        "{}Total:{} {}{:.2}ms{}",
-        ANSI_BOLD, ANSI_RESET, ANSI_ITALIC, total, ANSI_RESET
-    );
+    reporter.finished(total);
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::process::ExitStatusExt;
+
+    fn exited(code: i32) -> ExitStatus {
+        ExitStatus::from_raw(code << 8)
+    }
+
+    fn killed(signal: i32) -> ExitStatus {
+        ExitStatus::from_raw(signal)
+    }
+
+    fn failed(reason: &str) -> DayStatus {
+        DayStatus::Failed {
+            reason: reason.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_classify() {
+        let none = Limits::default();
+        let memory = Limits {
+            memory: Some(1 << 20),
+            ..Limits::default()
+        };
+        let cpu = Limits {
+            cpu: Some(2),
+            ..Limits::default()
+        };
+        let second = Duration::from_secs(1);
+        let panic = "\nthread 'main' panicked at src/lib.rs:54:7:\ncould not open input file\n";
+
+        assert_eq!(
+            classify(exited(0), "", &cpu, 1, second),
+            DayStatus::Finished
+        );
+        assert_eq!(
+            classify(exited(101), panic, &none, 1, second),
+            failed("thread 'main' panicked at src/lib.rs:54:7: could not open input file")
+        );
+        assert_eq!(
+            classify(killed(libc::SIGABRT), "", &none, 1, second),
+            failed("killed by SIGABRT")
+        );
+
+        let allocation = "memory allocation of 1024 bytes failed\n";
+        let spawn = "thread 'main' panicked at src/helpers/parallel.rs:53:27:\n\
+                     failed to spawn thread: Os { code: 11, kind: WouldBlock, message: \"\" }\n";
+        let loader = "01: error while loading shared libraries: libc.so.6: failed to map segment\n";
+        let overflow =
+            "\nthread 'main' has overflowed its stack\nfatal runtime error: stack overflow\n";
+        assert_eq!(
+            classify(killed(libc::SIGABRT), allocation, &memory, 1, second),
+            DayStatus::OutOfMemory
+        );
+        assert_eq!(
+            classify(exited(101), spawn, &memory, 1, second),
+            DayStatus::OutOfMemory
+        );
+        assert_eq!(
+            classify(exited(127), loader, &memory, 1, second),
+            DayStatus::OutOfMemory
+        );
+        assert_eq!(
+            classify(killed(libc::SIGABRT), overflow, &memory, 1, second),
+            failed("thread 'main' has overflowed its stack (killed by SIGABRT)")
+        );
+        assert_eq!(
+            classify(exited(127), "", &memory, 1, second),
+            failed("unknown error")
+        );
+        assert_eq!(
+            classify(exited(101), allocation, &none, 1, second),
+            failed("memory allocation of 1024 bytes failed")
+        );
+
+        assert_eq!(
+            classify(killed(libc::SIGXCPU), "", &cpu, 2, second),
+            DayStatus::TimedOut { part: 2 }
+        );
+        assert_eq!(
+            classify(killed(libc::SIGKILL), "", &cpu, 2, 3 * second),
+            DayStatus::TimedOut { part: 2 }
+        );
+        assert_eq!(
+            classify(killed(libc::SIGKILL), "", &cpu, 2, second),
+            failed("killed by SIGKILL")
+        );
+        assert_eq!(
+            classify(killed(libc::SIGXCPU), "", &none, 2, second),
+            failed("killed by SIGXCPU")
+        );
+    }
 }
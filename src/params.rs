@@ -0,0 +1,207 @@
+//! Named puzzle parameters that differ between the examples and the real input,
+//! e.g. the row to scan on day 15.
+//!
+//! A day declares its parameters with the value for the real input and, where it differs,
+//! the value for its example. Which one applies follows the last file read with
+//! [`crate::read_file`] on the current thread, so both `solve!` and the tests get the right
+//! values without passing them around. [`crate::helpers::par_map`] hands this on to its
+//! workers, while threads spawned otherwise have to [`inherit`] it before calling [`get`].
+//!
+//! Values can be overridden with `AOC_PARAMS` or by passing `--param`, e.g.
+//! `cargo solve 15 -- --param row=11`. Prefix the name with the day, as in `15.row=11`,
+//! to only override it for that day.
+use lazy_static::lazy_static;
+use std::cell::Cell;
+use std::env;
+
+pub const PARAMS_ENV: &str = "AOC_PARAMS";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    pub input: i64,
+    pub example: Option<i64>,
+}
+
+impl Param {
+    pub const fn new(name: &'static str, input: i64) -> Param {
+        Param {
+            name,
+            input,
+            example: None,
+        }
+    }
+
+    pub const fn example(self, value: i64) -> Param {
+        Param {
+            example: Some(value),
+            ..self
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Override {
+    day: Option<u8>,
+    name: String,
+    value: i64,
+}
+
+/// Parses `name=value` or `day.name=value`.
+fn parse_override(text: &str) -> Option<Override> {
+    let (name, value) = text.trim().split_once('=')?;
+    let (day, name) = match name.split_once('.') {
+        Some((day, name)) => (Some(day.parse().ok()?), name),
+        None => (None, name),
+    };
+
+    Some(Override {
+        day,
+        name: name.to_owned(),
+        value: value.parse().ok()?,
+    })
+}
+
+/// Checks that `text` is an override the solutions accept, e.g. before passing on a `--param`.
+pub fn is_valid_override(text: &str) -> bool {
+    parse_override(text).is_some()
+}
+
+/// Overrides from the environment first, so the ones on the command line take precedence.
+fn overrides_from(env: Option<String>, mut args: impl Iterator<Item = String>) -> Vec<Override> {
+    let from_env = env
+        .into_iter()
+        .flat_map(|list| list.split(',').map(str::to_owned).collect::<Vec<_>>());
+    let from_args = std::iter::from_fn(|| loop {
+        if args.next()? == "--param" {
+            return args.next();
+        }
+    });
+
+    from_env
+        .chain(from_args)
+        .filter(|text| !text.trim().is_empty())
+        .map(|text| parse_override(&text).unwrap_or_else(|| panic!("invalid parameter {:?}", text)))
+        .collect()
+}
+
+lazy_static! {
+    static ref OVERRIDES: Vec<Override> =
+        overrides_from(env::var(PARAMS_ENV).ok(), env::args().skip(1));
+}
+
+thread_local! {
+    static SOURCE: Cell<Option<(bool, u8)>> = const { Cell::new(None) };
+}
+
+/// Remembers which file the current thread is solving, called by [`crate::read_file`].
+pub fn set_source(folder: &str, day: u8) {
+    SOURCE.with(|source| source.set(Some((folder == "examples", day))));
+}
+
+/// The file being solved on the current thread.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Source(Option<(bool, u8)>);
+
+pub fn source() -> Source {
+    Source(SOURCE.with(Cell::get))
+}
+
+/// Makes the current thread use the parameters for `source`, e.g. in a worker that
+/// solves part of the same input.
+pub fn inherit(source: Source) {
+    SOURCE.with(|current| current.set(source.0));
+}
+
+fn resolve(params: &[Param], name: &str, overrides: &[Override]) -> i64 {
+    let param = params
+        .iter()
+        .find(|p| p.name == name)
+        .unwrap_or_else(|| panic!("unknown parameter {}", name));
+    let (is_example, day) = SOURCE.with(Cell::get).unzip();
+
+    let overridden = overrides
+        .iter()
+        .rev()
+        .find(|o| o.name == name && (o.day.is_none() || o.day == day));
+
+    match overridden {
+        Some(o) => o.value,
+        None if is_example == Some(true) => param.example.unwrap_or(param.input),
+        None => param.input,
+    }
+}
+
+/// The value of the parameter `name` for the file being solved on this thread.
+pub fn get<T: TryFrom<i64>>(params: &[Param], name: &str) -> T {
+    let value = resolve(params, name, &OVERRIDES);
+    crate::debug!("{} = {}", name, value);
+
+    T::try_from(value).unwrap_or_else(|_| panic!("{} = {} is out of range", name, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARAMS: &[Param] = &[
+        Param::new("row", 2_000_000).example(10),
+        Param::new("rounds", 20),
+    ];
+
+    #[test]
+    fn test_example_values() {
+        set_source("inputs", 15);
+        assert_eq!(resolve(PARAMS, "row", &[]), 2_000_000);
+        set_source("examples", 15);
+        assert_eq!(resolve(PARAMS, "row", &[]), 10);
+        assert_eq!(resolve(PARAMS, "rounds", &[]), 20);
+    }
+
+    #[test]
+    fn test_overrides() {
+        let overrides = overrides_from(
+            Some("row=11,14.rounds=1".to_owned()),
+            ["-v", "--param", "15.rounds=5", "--param", "row=12"]
+                .into_iter()
+                .map(String::from),
+        );
+        assert_eq!(overrides.len(), 4);
+
+        set_source("examples", 15);
+        assert_eq!(resolve(PARAMS, "row", &overrides), 12);
+        assert_eq!(resolve(PARAMS, "rounds", &overrides), 5);
+        set_source("inputs", 14);
+        assert_eq!(resolve(PARAMS, "rounds", &overrides), 1);
+    }
+
+    #[test]
+    fn test_inherit() {
+        set_source("examples", 15);
+        let rows = crate::helpers::par_map(0..4, |_| resolve(PARAMS, "row", &[]));
+        assert_eq!(rows, [10; 4]);
+
+        let source = source();
+        let row = std::thread::spawn(move || {
+            inherit(source);
+            resolve(PARAMS, "row", &[])
+        });
+        assert_eq!(row.join().unwrap(), 10);
+    }
+
+    #[test]
+    fn test_parse_override() {
+        assert_eq!(parse_override("x"), None);
+        assert_eq!(parse_override("a.b=1"), None);
+        assert!(!is_valid_override("row10"));
+        assert!(is_valid_override("15.row=10"));
+        assert_eq!(
+            parse_override(" 7.rocks=-3"),
+            Some(Override {
+                day: Some(7),
+                name: "rocks".to_owned(),
+                value: -3,
+            })
+        );
+    }
+}
@@ -0,0 +1,535 @@
+use crate::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fmt::Write;
+use std::io::IsTerminal;
+use std::time::Duration;
+
+/// Environment variable used to select a reporter, see [`from_env`].
+pub const REPORTER_ENV: &str = "AOC_REPORTER";
+
+/// The result of running a single part of a day.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartResult {
+    pub part: u8,
+    pub answer: Option<String>,
+    #[serde(with = "nanos")]
+    pub elapsed: Duration,
+}
+
+/// The result of a single function that solves both parts at once.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BothResult {
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+    #[serde(with = "nanos")]
+    pub elapsed: Duration,
+}
+
+/// How running a whole day ended, as seen by the runner.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum DayStatus {
+    Finished,
+    NotSolved,
+    TimedOut { part: u8 },
+    OutOfMemory,
+    Failed { reason: String },
+}
+
+/// Everything a reporter is told about. Also used as the line-based protocol between
+/// the runner and the solution binaries, which always report through [`JsonReporter`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    DayStarted {
+        day: u8,
+    },
+    Parsed {
+        #[serde(with = "nanos")]
+        elapsed: Duration,
+    },
+    PartStarted {
+        part: u8,
+    },
+    PartFinished(PartResult),
+    BothStarted,
+    BothFinished(BothResult),
+    DayFinished {
+        day: u8,
+        status: DayStatus,
+    },
+    Finished {
+        #[serde(with = "nanos")]
+        total: Duration,
+    },
+}
+
+pub trait Reporter {
+    fn day_started(&mut self, _day: u8) {}
+
+    /// Called once the input of a [`Solution`](crate::Solution) has been parsed.
+    fn parsed(&mut self, _elapsed: Duration) {}
+
+    fn part_started(&mut self, _part: u8) {}
+
+    fn part_finished(&mut self, result: &PartResult);
+
+    fn both_started(&mut self) {}
+
+    /// Reports both answers as separate parts, attributing the combined time to part 1.
+    fn both_finished(&mut self, result: &BothResult) {
+        self.part_finished(&PartResult {
+            part: 1,
+            answer: result.part_one.clone(),
+            elapsed: result.elapsed,
+        });
+        self.part_finished(&PartResult {
+            part: 2,
+            answer: result.part_two.clone(),
+            elapsed: Duration::ZERO,
+        });
+    }
+
+    /// Output of a solution that is not a result, e.g. debug prints.
+    fn output(&mut self, _line: &str) {}
+
+    fn day_finished(&mut self, _day: u8, _status: &DayStatus) {}
+
+    fn finished(&mut self, _total: Duration) {}
+
+    fn event(&mut self, event: &Event) {
+        match event {
+            Event::DayStarted { day } => self.day_started(*day),
+            Event::Parsed { elapsed } => self.parsed(*elapsed),
+            Event::PartStarted { part } => self.part_started(*part),
+            Event::PartFinished(result) => self.part_finished(result),
+            Event::BothStarted => self.both_started(),
+            Event::BothFinished(result) => self.both_finished(result),
+            Event::DayFinished { day, status } => self.day_finished(*day, status),
+            Event::Finished { total } => self.finished(*total),
+        }
+    }
+}
+
+/// Picks a reporter by name: `pretty`, `plain`, `json` or `junit`.
+pub fn by_name(name: &str) -> Option<Box<dyn Reporter>> {
+    match name {
+        "pretty" => Some(Box::new(TextReporter::pretty())),
+        "plain" => Some(Box::new(TextReporter::plain())),
+        "json" => Some(Box::new(JsonReporter)),
+        "junit" => Some(Box::new(JunitReporter::default())),
+        _ => None,
+    }
+}
+
+/// Picks the reporter named by `AOC_REPORTER`. Falls back to the pretty reporter,
+/// or the plain one if `NO_COLOR` is set or stdout is not a terminal.
+///
+/// `junit` is rejected, as a single solution binary reports each part separately and
+/// cannot write a complete document. Use `cargo all -- --format junit` instead.
+pub fn from_env() -> Box<dyn Reporter> {
+    let name = env::var(REPORTER_ENV).ok();
+    if name.as_deref() == Some("junit") {
+        eprintln!(
+            "{}=junit is only supported by the runner, use `cargo all -- --format junit`.",
+            REPORTER_ENV
+        );
+        std::process::exit(1);
+    }
+
+    name.and_then(|name| by_name(&name))
+        .unwrap_or_else(|| Box::new(TextReporter::auto()))
+}
+
+/// Human readable output, either with emoji and ANSI escape sequences or without.
+pub struct TextReporter {
+    colored: bool,
+}
+
+impl TextReporter {
+    pub fn pretty() -> TextReporter {
+        TextReporter { colored: true }
+    }
+
+    pub fn plain() -> TextReporter {
+        TextReporter { colored: false }
+    }
+
+    pub fn auto() -> TextReporter {
+        let no_color = env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+        TextReporter {
+            colored: !no_color && std::io::stdout().is_terminal(),
+        }
+    }
+
+    fn style<'a>(&self, ansi: &'a str) -> &'a str {
+        if self.colored {
+            ansi
+        } else {
+            ""
+        }
+    }
+}
+
+impl Reporter for TextReporter {
+    fn day_started(&mut self, day: u8) {
+        println!("----------");
+        println!(
+            "{}| Day {:02} |{}",
+            self.style(ANSI_BOLD),
+            day,
+            self.style(ANSI_RESET)
+        );
+        println!("----------");
+    }
+
+    fn parsed(&mut self, elapsed: Duration) {
+        if self.colored {
+            println!(
+                "🎄 {}Parse{} 🎄 {}(elapsed: {:.2?}){}",
+                ANSI_BOLD, ANSI_RESET, ANSI_ITALIC, elapsed, ANSI_RESET
+            );
+        } else {
+            println!("Parse (elapsed: {:.2?})", elapsed);
+        }
+    }
+
+    fn part_started(&mut self, part: u8) {
+        if self.colored {
+            println!("🎄 {}Part {}{} 🎄", ANSI_BOLD, part, ANSI_RESET);
+        } else {
+            println!("Part {}", part);
+        }
+    }
+
+    fn part_finished(&mut self, result: &PartResult) {
+        match &result.answer {
+            Some(answer) => println!(
+                "{} {}(elapsed: {:.2?}){}",
+                answer,
+                self.style(ANSI_ITALIC),
+                result.elapsed,
+                self.style(ANSI_RESET)
+            ),
+            None => println!("not solved."),
+        }
+    }
+
+    fn both_started(&mut self) {
+        if self.colored {
+            println!("🎄 {}Part 1 + 2{} 🎄", ANSI_BOLD, ANSI_RESET);
+        } else {
+            println!("Part 1 + 2");
+        }
+    }
+
+    fn both_finished(&mut self, result: &BothResult) {
+        for (part, answer) in [(1, &result.part_one), (2, &result.part_two)] {
+            match answer {
+                Some(answer) => println!("{}: {}", part, answer),
+                None => println!("{}: not solved.", part),
+            }
+        }
+        println!(
+            "{}(elapsed: {:.2?}){}",
+            self.style(ANSI_ITALIC),
+            result.elapsed,
+            self.style(ANSI_RESET)
+        );
+    }
+
+    fn output(&mut self, line: &str) {
+        println!("{}", line);
+    }
+
+    fn day_finished(&mut self, _day: u8, status: &DayStatus) {
+        match status {
+            DayStatus::Finished => {}
+            DayStatus::NotSolved => println!("Not solved."),
+            DayStatus::TimedOut { part: 0 } => println!("Timed out."),
+            DayStatus::TimedOut { part } => println!("Timed out in part {}.", part),
+            DayStatus::OutOfMemory => println!("Out of memory."),
+            DayStatus::Failed { reason } => println!("Failed: {}", reason),
+        }
+    }
+
+    fn finished(&mut self, total: Duration) {
+        println!(
+            "{}Total:{} {}{:.2}ms{}",
+            self.style(ANSI_BOLD),
+            self.style(ANSI_RESET),
+            self.style(ANSI_ITALIC),
+            total.as_secs_f64() * 1000_f64,
+            self.style(ANSI_RESET)
+        );
+    }
+}
+
+/// Writes every event as one JSON object per line.
+pub struct JsonReporter;
+
+impl JsonReporter {
+    fn write(&self, event: &Event) {
+        println!("{}", serde_json::to_string(event).unwrap());
+    }
+}
+
+impl Reporter for JsonReporter {
+    fn day_started(&mut self, day: u8) {
+        self.write(&Event::DayStarted { day });
+    }
+
+    fn parsed(&mut self, elapsed: Duration) {
+        self.write(&Event::Parsed { elapsed });
+    }
+
+    fn part_started(&mut self, part: u8) {
+        self.write(&Event::PartStarted { part });
+    }
+
+    fn part_finished(&mut self, result: &PartResult) {
+        self.write(&Event::PartFinished(result.clone()));
+    }
+
+    fn both_started(&mut self) {
+        self.write(&Event::BothStarted);
+    }
+
+    fn both_finished(&mut self, result: &BothResult) {
+        self.write(&Event::BothFinished(result.clone()));
+    }
+
+    fn day_finished(&mut self, day: u8, status: &DayStatus) {
+        self.write(&Event::DayFinished {
+            day,
+            status: status.clone(),
+        });
+    }
+
+    fn finished(&mut self, total: Duration) {
+        self.write(&Event::Finished { total });
+    }
+}
+
+/// Collects all days and writes a JUnit XML document once the run is finished.
+#[derive(Default)]
+pub struct JunitReporter {
+    suites: Vec<Suite>,
+}
+
+struct Suite {
+    day: u8,
+    parse_time: Duration,
+    cases: Vec<Case>,
+}
+
+struct Case {
+    name: String,
+    time: Duration,
+    answer: Option<String>,
+    failure: Option<String>,
+}
+
+impl Case {
+    fn write(&self, xml: &mut String, classname: &str) {
+        write!(
+            xml,
+            "<testcase classname=\"{}\" name=\"{}\" time=\"{:.6}\"",
+            classname,
+            self.name,
+            self.time.as_secs_f64()
+        )
+        .unwrap();
+        match (&self.failure, &self.answer) {
+            (Some(failure), _) => write!(
+                xml,
+                "><failure message=\"{}\"/></testcase>",
+                escape_xml(failure)
+            ),
+            (None, Some(answer)) => write!(
+                xml,
+                "><system-out>{}</system-out></testcase>",
+                escape_xml(answer)
+            ),
+            (None, None) => write!(xml, "><skipped message=\"not solved\"/></testcase>"),
+        }
+        .unwrap();
+    }
+}
+
+impl Reporter for JunitReporter {
+    fn day_started(&mut self, day: u8) {
+        self.suites.push(Suite {
+            day,
+            parse_time: Duration::ZERO,
+            cases: Vec::new(),
+        });
+    }
+
+    fn parsed(&mut self, elapsed: Duration) {
+        if let Some(suite) = self.suites.last_mut() {
+            suite.parse_time = elapsed;
+        }
+    }
+
+    fn part_finished(&mut self, result: &PartResult) {
+        let case = Case {
+            name: format!("part {}", result.part),
+            time: result.elapsed,
+            answer: result.answer.clone(),
+            failure: None,
+        };
+
+        if let Some(suite) = self.suites.last_mut() {
+            suite.cases.push(case);
+        }
+    }
+
+    fn day_finished(&mut self, _day: u8, status: &DayStatus) {
+        let suite = match self.suites.last_mut() {
+            Some(suite) => suite,
+            None => return,
+        };
+
+        let (name, failure) = match status {
+            DayStatus::Finished => return,
+            DayStatus::NotSolved => {
+                suite.cases.push(Case {
+                    name: "solution".to_string(),
+                    time: Duration::ZERO,
+                    answer: None,
+                    failure: None,
+                });
+                return;
+            }
+            DayStatus::TimedOut { part } => (format!("part {}", part), "timed out".to_string()),
+            DayStatus::OutOfMemory => ("solution".to_string(), "out of memory".to_string()),
+            DayStatus::Failed { reason } => ("solution".to_string(), reason.clone()),
+        };
+
+        suite.cases.push(Case {
+            name,
+            time: Duration::ZERO,
+            answer: None,
+            failure: Some(failure),
+        });
+    }
+
+    fn finished(&mut self, total: Duration) {
+        let failures = |suite: &Suite| suite.cases.iter().filter(|c| c.failure.is_some()).count();
+        let skipped = |suite: &Suite| {
+            suite
+                .cases
+                .iter()
+                .filter(|c| c.failure.is_none() && c.answer.is_none())
+                .count()
+        };
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        writeln!(
+            xml,
+            "<testsuites name=\"advent_of_code\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.6}\">",
+            self.suites.iter().map(|s| s.cases.len()).sum::<usize>(),
+            self.suites.iter().map(failures).sum::<usize>(),
+            self.suites.iter().map(skipped).sum::<usize>(),
+            total.as_secs_f64()
+        )
+        .unwrap();
+
+        for suite in &self.suites {
+            let classname = format!("day{:02}", suite.day);
+            writeln!(
+                xml,
+                "  <testsuite name=\"Day {:02}\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.6}\">",
+                suite.day,
+                suite.cases.len(),
+                failures(suite),
+                skipped(suite),
+                (suite.parse_time + suite.cases.iter().map(|c| c.time).sum::<Duration>())
+                    .as_secs_f64()
+            )
+            .unwrap();
+            for case in &suite.cases {
+                xml.push_str("    ");
+                case.write(&mut xml, &classname);
+                xml.push('\n');
+            }
+            xml.push_str("  </testsuite>\n");
+        }
+
+        xml.push_str("</testsuites>");
+        println!("{}", xml);
+    }
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+mod nanos {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(duration.as_nanos() as u64)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_nanos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_round_trip() {
+        let events = [
+            Event::Parsed {
+                elapsed: Duration::from_millis(2),
+            },
+            Event::PartStarted { part: 1 },
+            Event::PartFinished(PartResult {
+                part: 1,
+                answer: Some("24000".to_string()),
+                elapsed: Duration::from_nanos(1_110),
+            }),
+            Event::BothStarted,
+            Event::BothFinished(BothResult {
+                part_one: Some("110".to_string()),
+                part_two: None,
+                elapsed: Duration::from_micros(40),
+            }),
+            Event::DayFinished {
+                day: 19,
+                status: DayStatus::TimedOut { part: 2 },
+            },
+        ];
+
+        for event in events {
+            let line = serde_json::to_string(&event).unwrap();
+            assert_eq!(serde_json::from_str::<Event>(&line).unwrap(), event);
+        }
+
+        assert_eq!(
+            serde_json::to_string(&Event::PartFinished(PartResult {
+                part: 2,
+                answer: None,
+                elapsed: Duration::from_micros(3),
+            }))
+            .unwrap(),
+            r#"{"event":"part_finished","part":2,"answer":null,"elapsed":3000}"#
+        );
+    }
+
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(escape_xml("<a & \"b\">"), "&lt;a &amp; &quot;b&quot;&gt;");
+    }
+}
@@ -0,0 +1,59 @@
+use crate::reporter::{BothResult, PartResult, Reporter};
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+/// A day that parses its input once and shares the result between both parts.
+///
+/// Run it with `advent_of_code::solve!(DayNN, input)`, which reports the time spent
+/// parsing separately from the time spent in each part.
+pub trait Solution {
+    type Input;
+    type PartOne: Display;
+    type PartTwo: Display;
+
+    fn parse(input: &str) -> Self::Input;
+
+    fn part_one(input: &Self::Input) -> Option<Self::PartOne>;
+
+    fn part_two(input: &Self::Input) -> Option<Self::PartTwo>;
+}
+
+pub fn timed<T>(func: impl FnOnce() -> T) -> (T, Duration) {
+    let timer = Instant::now();
+    let result = func();
+    (result, timer.elapsed())
+}
+
+pub fn run_part<T: Display>(
+    reporter: &mut dyn Reporter,
+    part: u8,
+    func: impl FnOnce() -> Option<T>,
+) {
+    reporter.part_started(part);
+    let (result, elapsed) = timed(func);
+    reporter.part_finished(&PartResult {
+        part,
+        answer: result.map(|result| result.to_string()),
+        elapsed,
+    });
+}
+
+pub fn run<S: Solution>(reporter: &mut dyn Reporter, input: &str) {
+    let (parsed, elapsed) = timed(|| S::parse(input));
+    reporter.parsed(elapsed);
+    run_part(reporter, 1, || S::part_one(&parsed));
+    run_part(reporter, 2, || S::part_two(&parsed));
+}
+
+pub fn run_both<A: Display, B: Display>(
+    reporter: &mut dyn Reporter,
+    func: impl FnOnce() -> (Option<A>, Option<B>),
+) {
+    reporter.both_started();
+    let ((part_one, part_two), elapsed) = timed(func);
+    reporter.both_finished(&BothResult {
+        part_one: part_one.map(|result| result.to_string()),
+        part_two: part_two.map(|result| result.to_string()),
+        elapsed,
+    });
+}